@@ -0,0 +1,38 @@
+//! Cost of the symbol queries compared with a full dataset.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use spglib::cell::Cell;
+use spglib::dataset::Dataset;
+
+// Rutile TiO2 in P4_2/mnm, the same structure as the unit tests use in src/fixtures.rs.
+fn rutile() -> Cell {
+    let x = 0.3048;
+    let lattice = [[4.59, 0., 0.], [0., 4.59, 0.], [0., 0., 2.96]];
+    let positions = [
+        [0., 0., 0.],
+        [0.5, 0.5, 0.5],
+        [x, x, 0.],
+        [-x, -x, 0.],
+        [0.5 + x, 0.5 - x, 0.5],
+        [0.5 - x, 0.5 + x, 0.5],
+    ];
+    let types = [22, 22, 8, 8, 8, 8];
+    Cell::new(&lattice, &positions, &types)
+}
+
+fn symbols(c: &mut Criterion) {
+    let cell = rutile();
+    let mut group = c.benchmark_group("rutile");
+    group.bench_function("dataset", |b| b.iter(|| Dataset::new(&cell, 1e-5).unwrap()));
+    group.bench_function("international_symbol", |b| {
+        b.iter(|| cell.international_symbol(1e-5).unwrap())
+    });
+    group.bench_function("schoenflies_symbol", |b| {
+        b.iter(|| cell.schoenflies_symbol(1e-5).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, symbols);
+criterion_main!(benches);
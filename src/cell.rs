@@ -1,10 +1,25 @@
 //! Representation of a bounded atomic structure.
 
-use std::os::raw::c_int;
+use std::ffi::{CStr, CString};
+use std::os::raw::{c_char, c_int};
 
 use spglib_sys as ffi;
 
-use crate::error::SpglibError;
+use crate::dataset::Dataset;
+use crate::error::{CellError, Error, SpglibError};
+use crate::lattice::LatticeParameters;
+use crate::math::{column, det, dot, inverse, mat_vec, transpose};
+use crate::mesh::{mesh_size, ReciprocalMesh};
+use crate::spacegroup::SpacegroupSymbol;
+use crate::symmetry::{SpinSymmetry, Symmetry};
+use crate::tolerance::Tolerance;
+
+// Smallest volume, relative to the product of the lattice vector lengths, of a lattice that is
+// not considered degenerate.
+const DEGENERACY_TOLERANCE: f64 = 1e-8;
+
+// Rotations and translations of space group operations.
+type Operations = (Vec<[[i32; 3]; 3]>, Vec<[f64; 3]>);
 
 /// Atomic structure with lattice bounds.
 #[derive(Clone, Debug)]
@@ -30,6 +45,285 @@ impl Cell {
         }
     }
 
+    /// Returns a new cell after checking that it can be passed to spglib.
+    ///
+    /// See [`validate`](Cell::validate) for the checks, with atoms closer than `symprec`
+    /// considered to occupy the same site.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spglib::cell::Cell;
+    /// use spglib::error::CellError;
+    ///
+    /// let lattice = [[4.0, 0.0, 0.0], [0.0, 4.0, 0.0], [0.0, 0.0, 4.0]];
+    /// assert!(Cell::try_new(&lattice, &[[0.0, 0.0, 0.0]], &[1], 1e-5).is_ok());
+    /// let err = Cell::try_new(&lattice, &[[0.0, 0.0, 0.0]], &[1, 2], 1e-5).unwrap_err();
+    /// assert_eq!(err, CellError::LengthMismatch { positions: 1, types: 2 });
+    /// ```
+    pub fn try_new(
+        lattice: &[[f64; 3]; 3],
+        positions: &[[f64; 3]],
+        types: &[i32],
+        symprec: f64,
+    ) -> Result<Cell, CellError> {
+        let cell = Cell::new(lattice, positions, types);
+        cell.validate(symprec)?;
+        Ok(cell)
+    }
+
+    /// Checks that the cell can be passed to spglib.
+    ///
+    /// Fails if the numbers of positions and types differ, if any value is NaN or infinite,
+    /// if the lattice vectors are (nearly) linearly dependent or form a left-handed basis,
+    /// or if two atoms of the same type are closer than `symprec` taking periodic images into
+    /// account.
+    /// Every method calling into spglib runs these checks with its own tolerance first.
+    pub fn validate(&self, symprec: f64) -> Result<(), CellError> {
+        if self.positions.len() != self.types.len() {
+            return Err(CellError::LengthMismatch {
+                positions: self.positions.len(),
+                types: self.types.len(),
+            });
+        }
+        if !self.positions.iter().flatten().all(|x| x.is_finite()) {
+            return Err(CellError::NonFinite);
+        }
+        validate_lattice(&self.lattice)?;
+        let reciprocal = self.reciprocal_lattice();
+        for i in 0..self.positions.len() {
+            for j in i + 1..self.positions.len() {
+                if self.types[i] != self.types[j] {
+                    continue;
+                }
+                let d = self.minimum_image(i, j, reciprocal.as_ref());
+                let distance = dot(&d, &d).sqrt();
+                if distance < symprec {
+                    return Err(CellError::DuplicateSites {
+                        first: i,
+                        second: j,
+                        distance,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // Validates the cell before it is passed to `function`.
+    pub(crate) fn check(&self, function: &'static str, tolerance: Tolerance) -> Result<(), Error> {
+        self.validate(tolerance.symprec).map_err(|err| {
+            Error::invalid_cell(err, function).with_inputs(self.positions.len(), tolerance)
+        })
+    }
+
+    /// Returns a new cell from atomic positions in Cartesian coordinates, or `None` if the lattice
+    /// is singular.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spglib::cell::Cell;
+    ///
+    /// let lattice = [[4.0, 0.0, 0.0], [0.0, 4.0, 0.0], [0.0, 0.0, 2.0]];
+    /// let cell = Cell::from_cartesian(&lattice, &[[2.0, 1.0, 1.0]], &[1]).unwrap();
+    /// assert_eq!(cell.positions, vec![[0.5, 0.25, 0.5]]);
+    /// assert_eq!(cell.cartesian_positions(), vec![[2.0, 1.0, 1.0]]);
+    /// ```
+    pub fn from_cartesian(
+        lattice: &[[f64; 3]; 3],
+        positions: &[[f64; 3]],
+        types: &[i32],
+    ) -> Option<Cell> {
+        let inv = inverse(lattice)?;
+        let positions: Vec<[f64; 3]> = positions.iter().map(|r| mat_vec(&inv, r)).collect();
+        Some(Cell::new(lattice, &positions, types))
+    }
+
+    /// Returns the atomic positions in Cartesian coordinates.
+    pub fn cartesian_positions(&self) -> Vec<[f64; 3]> {
+        self.positions
+            .iter()
+            .map(|x| mat_vec(&self.lattice, x))
+            .collect()
+    }
+
+    /// Wraps the fractional positions into `[0, 1)`.
+    ///
+    /// Coordinates that are within `tolerance` below 1, such as `0.9999999` or `-1e-9`,
+    /// are set to 0 so that atoms on a face of the cell are not moved to the opposite face
+    /// by rounding errors.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spglib::cell::Cell;
+    ///
+    /// let lattice = [[4.0, 0.0, 0.0], [0.0, 4.0, 0.0], [0.0, 0.0, 4.0]];
+    /// let mut cell = Cell::new(&lattice, &[[1.25, -0.25, -1e-9]], &[1]);
+    /// cell.wrap_positions(1e-6);
+    /// assert_eq!(cell.positions, vec![[0.25, 0.75, 0.0]]);
+    /// ```
+    pub fn wrap_positions(&mut self, tolerance: f64) {
+        for x in self.positions.iter_mut().flatten() {
+            let wrapped = *x - x.floor();
+            *x = if wrapped >= 1.0 - tolerance {
+                0.0
+            } else {
+                wrapped
+            };
+        }
+    }
+
+    /// Returns the shortest Cartesian vector from atom `i` to any periodic image of atom `j`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spglib::cell::Cell;
+    ///
+    /// let lattice = [[4.0, 0.0, 0.0], [0.0, 4.0, 0.0], [0.0, 0.0, 4.0]];
+    /// let cell = Cell::new(&lattice, &[[0.1, 0.0, 0.0], [0.9, 0.4, 0.0]], &[1, 1]);
+    /// let d = cell.displacement(0, 1);
+    /// assert!((d[0] + 0.8).abs() < 1e-12 && (d[1] - 1.6).abs() < 1e-12);
+    /// assert!((cell.distance(0, 1) - 0.8f64.hypot(1.6)).abs() < 1e-12);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if either index is out of range.
+    pub fn displacement(&self, i: usize, j: usize) -> [f64; 3] {
+        self.minimum_image(i, j, self.reciprocal_lattice().as_ref())
+    }
+
+    // Returns the displacement from atom `i` to the nearest image of atom `j`, searching the
+    // images within the range allowed by the reciprocal lattice, or only the rounded difference
+    // of fractional coordinates for a singular lattice.
+    fn minimum_image(&self, i: usize, j: usize, reciprocal: Option<&[[f64; 3]; 3]>) -> [f64; 3] {
+        let diff = [0, 1, 2].map(|k| {
+            let d = self.positions[j][k] - self.positions[i][k];
+            d - d.round()
+        });
+        let cartesian = |shift: [f64; 3]| {
+            let x = [0, 1, 2].map(|k| diff[k] + shift[k]);
+            mat_vec(&self.lattice, &x)
+        };
+        let mut best = cartesian([0.0; 3]);
+        let reciprocal = match reciprocal {
+            Some(reciprocal) => reciprocal,
+            None => return best,
+        };
+        // a lattice vector that shortens `best` has fractional coordinates no larger than
+        // |best| times the length of the corresponding reciprocal vector
+        let radius = dot(&best, &best).sqrt();
+        let range = [0, 1, 2].map(|k| {
+            let b = column(reciprocal, k);
+            (radius * dot(&b, &b).sqrt()).ceil() as i32
+        });
+        for n0 in -range[0]..=range[0] {
+            for n1 in -range[1]..=range[1] {
+                for n2 in -range[2]..=range[2] {
+                    let candidate = cartesian([n0 as f64, n1 as f64, n2 as f64]);
+                    if dot(&candidate, &candidate) < dot(&best, &best) {
+                        best = candidate;
+                    }
+                }
+            }
+        }
+        best
+    }
+
+    /// Returns the distance between atom `i` and the nearest periodic image of atom `j`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either index is out of range.
+    pub fn distance(&self, i: usize, j: usize) -> f64 {
+        let d = self.displacement(i, j);
+        dot(&d, &d).sqrt()
+    }
+
+    /// Returns a new cell whose lattice is built from lattice parameters in the standard
+    /// orientation, with `a` along x and `b` in the xy plane, or `None` if the parameters do not
+    /// describe a cell with positive volume.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spglib::cell::Cell;
+    /// use spglib::lattice::LatticeParameters;
+    ///
+    /// let parameters = LatticeParameters::new(4.0, 4.0, 6.0, 90.0, 90.0, 90.0);
+    /// let cell = Cell::from_lattice_parameters(&parameters, &[[0.0, 0.0, 0.0]], &[1]).unwrap();
+    /// assert_eq!(cell.volume(), 96.0);
+    /// ```
+    pub fn from_lattice_parameters(
+        parameters: &LatticeParameters,
+        positions: &[[f64; 3]],
+        types: &[i32],
+    ) -> Option<Cell> {
+        Some(Cell::new(&parameters.lattice()?, positions, types))
+    }
+
+    /// Returns the volume of the cell, which is negative if the lattice vectors form a
+    /// left-handed basis.
+    pub fn volume(&self) -> f64 {
+        det(&self.lattice)
+    }
+
+    /// Returns the lengths of the lattice vectors and the angles between them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spglib::cell::Cell;
+    ///
+    /// let lattice = [[0.0, 2.0, 2.0], [2.0, 0.0, 2.0], [2.0, 2.0, 0.0]];
+    /// let cell = Cell::new(&lattice, &[[0.0, 0.0, 0.0]], &[1]);
+    /// let parameters = cell.lattice_parameters();
+    /// assert!((parameters.a - 8f64.sqrt()).abs() < 1e-12);
+    /// assert!((parameters.gamma - 60.0).abs() < 1e-12);
+    /// ```
+    pub fn lattice_parameters(&self) -> LatticeParameters {
+        LatticeParameters::from_lattice(&self.lattice)
+    }
+
+    /// Returns the metric tensor, whose entry `[i][j]` is the dot product of lattice vectors
+    /// `i` and `j`.
+    pub fn metric_tensor(&self) -> [[f64; 3]; 3] {
+        let vectors = [0, 1, 2].map(|j| column(&self.lattice, j));
+        vectors.map(|a| vectors.map(|b| dot(&a, &b)))
+    }
+
+    /// Returns the reciprocal lattice vectors as the columns of a matrix, normalized so that the
+    /// dot product of lattice vector `i` and reciprocal vector `j` is 1 if `i == j` and 0
+    /// otherwise, or `None` if the lattice is singular.
+    ///
+    /// This is the convention used by [`GridMesh`](crate::grid::GridMesh).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spglib::cell::Cell;
+    ///
+    /// let lattice = [[4.0, 0.0, 0.0], [0.0, 4.0, 0.0], [0.0, 0.0, 2.0]];
+    /// let cell = Cell::new(&lattice, &[[0.0, 0.0, 0.0]], &[1]);
+    /// assert_eq!(
+    ///     cell.reciprocal_lattice().unwrap(),
+    ///     [[0.25, 0.0, 0.0], [0.0, 0.25, 0.0], [0.0, 0.0, 0.5]]
+    /// );
+    /// ```
+    pub fn reciprocal_lattice(&self) -> Option<[[f64; 3]; 3]> {
+        Some(transpose(&inverse(&self.lattice)?))
+    }
+
+    /// Returns the reciprocal lattice vectors including the factor of 2π, as used in physics,
+    /// or `None` if the lattice is singular.
+    pub fn reciprocal_lattice_2pi(&self) -> Option<[[f64; 3]; 3]> {
+        let reciprocal = self.reciprocal_lattice()?;
+        Some(reciprocal.map(|row| row.map(|x| 2.0 * std::f64::consts::PI * x)))
+    }
+
     /// Standardizes the cell with a symmetry search.
     /// Refer to the full documentation of the implementation [here](https://spglib.github.io/spglib/api.html#spg-standardize-cell).
     ///
@@ -65,53 +359,1105 @@ impl Cell {
         &mut self,
         to_primitive: bool,
         no_idealize: bool,
-        symprec: f64,
-    ) -> Result<(), SpglibError> {
-        let to_primitive = if to_primitive { 1 } else { 0 };
-        let no_idealize = if no_idealize { 1 } else { 0 };
+        tolerance: impl Into<Tolerance>,
+    ) -> Result<(), Error> {
+        let (cell, _) = self.standardized(to_primitive, no_idealize, tolerance)?;
+        *self = cell;
+        Ok(())
+    }
+
+    /// Returns a standardized copy of the cell along with the atom index mapping.
+    /// Refer to the full documentation of the implementation [here](https://spglib.github.io/spglib/api.html#spg-standardize-cell).
+    ///
+    /// The mapping holds, for each atom of this cell, the index of the first atom in the new cell
+    /// which is equivalent to it by a lattice translation of the primitive cell.
+    ///
+    /// # Example
+    ///
+    /// Standardize a primitive FCC cell into the conventional cell.
+    ///
+    /// ```
+    /// use spglib::cell::Cell;
+    ///
+    /// let lattice = [
+    ///     [0.0, 2.0, 2.0],
+    ///     [2.0, 0.0, 2.0],
+    ///     [2.0, 2.0, 0.0]
+    /// ];
+    /// let positions = [[0.0, 0.0, 0.0]];
+    /// let types = [1];
+    /// let cell = Cell::new(&lattice, &positions, &types);
+    /// let (std_cell, mapping) = cell.standardized(false, false, 1.0e-6).unwrap();
+    /// assert_eq!(std_cell.positions.len(), 4);
+    /// assert_eq!(std_cell.types, vec![1, 1, 1, 1]);
+    /// assert_eq!(mapping, vec![0]);
+    /// ```
+    pub fn standardized(
+        &self,
+        to_primitive: bool,
+        no_idealize: bool,
+        tolerance: impl Into<Tolerance>,
+    ) -> Result<(Cell, Vec<usize>), Error> {
+        let tolerance = tolerance.into();
+        self.check("spgat_standardize_cell", tolerance)?;
+        let n_atoms = self.positions.len();
+        // conventional cells of centered lattices hold up to 4 times as many atoms
+        let (mut lattice, mut positions, mut types) = self.buffers(4 * n_atoms);
+        let res = unsafe {
+            ffi::spgat_standardize_cell(
+                lattice.as_mut_ptr(),
+                positions.as_mut_ptr(),
+                types.as_mut_ptr(),
+                n_atoms as c_int,
+                to_primitive as c_int,
+                no_idealize as c_int,
+                tolerance.symprec,
+                tolerance.raw_angle_tolerance(),
+            )
+        };
+        if res == 0 {
+            return Err(Error::last("spgat_standardize_cell").with_inputs(n_atoms, tolerance));
+        }
+        positions.truncate(res as usize);
+        types.truncate(res as usize);
+        let cell = Cell {
+            lattice,
+            positions,
+            types,
+        };
+        let mapping = self.mapping_to(&cell, !no_idealize, "spgat_standardize_cell", tolerance)?;
+        Ok((cell, mapping))
+    }
+
+    /// Returns the standardized primitive cell along with the atom index mapping.
+    /// Refer to the full documentation of the implementation [here](https://spglib.github.io/spglib/api.html#spg-find-primitive).
+    ///
+    /// The mapping holds, for each atom of this cell, the index of its image in the primitive cell.
+    ///
+    /// # Example
+    ///
+    /// Find the primitive cell of a conventional FCC cell.
+    ///
+    /// ```
+    /// use spglib::cell::Cell;
+    ///
+    /// let lattice = [
+    ///     [4.0, 0.0, 0.0],
+    ///     [0.0, 4.0, 0.0],
+    ///     [0.0, 0.0, 4.0]
+    /// ];
+    /// let positions = [
+    ///     [0.0, 0.0, 0.0],
+    ///     [0.0, 0.5, 0.5],
+    ///     [0.5, 0.0, 0.5],
+    ///     [0.5, 0.5, 0.0]
+    /// ];
+    /// let types = [1, 1, 1, 1];
+    /// let cell = Cell::new(&lattice, &positions, &types);
+    /// let (primitive, mapping) = cell.primitive(1.0e-6).unwrap();
+    /// assert_eq!(primitive.positions.len(), 1);
+    /// assert_eq!(mapping, vec![0, 0, 0, 0]);
+    /// ```
+    pub fn primitive(&self, tolerance: impl Into<Tolerance>) -> Result<(Cell, Vec<usize>), Error> {
+        let tolerance = tolerance.into();
+        self.check("spgat_find_primitive", tolerance)?;
+        let n_atoms = self.positions.len();
+        let (mut lattice, mut positions, mut types) = self.buffers(n_atoms);
+        let res = unsafe {
+            ffi::spgat_find_primitive(
+                lattice.as_mut_ptr(),
+                positions.as_mut_ptr(),
+                types.as_mut_ptr(),
+                n_atoms as c_int,
+                tolerance.symprec,
+                tolerance.raw_angle_tolerance(),
+            )
+        };
+        if res == 0 {
+            return Err(Error::last("spgat_find_primitive").with_inputs(n_atoms, tolerance));
+        }
+        positions.truncate(res as usize);
+        types.truncate(res as usize);
+        let cell = Cell {
+            lattice,
+            positions,
+            types,
+        };
+        let mapping = self.mapping_to(&cell, true, "spgat_find_primitive", tolerance)?;
+        Ok((cell, mapping))
+    }
+
+    /// Returns the refined conventional cell along with the atom index mapping.
+    /// Refer to the full documentation of the implementation [here](https://spglib.github.io/spglib/api.html#spg-refine-cell).
+    ///
+    /// The mapping holds, for each atom of this cell, the index of the first atom in the refined cell
+    /// which is equivalent to it by a lattice translation of the primitive cell.
+    ///
+    /// # Example
+    ///
+    /// Refine a primitive rock salt cell.
+    ///
+    /// ```
+    /// use spglib::cell::Cell;
+    ///
+    /// let lattice = [
+    ///     [0.0, 2.8, 2.8],
+    ///     [2.8, 0.0, 2.8],
+    ///     [2.8, 2.8, 0.0]
+    /// ];
+    /// let positions = [[0.0, 0.0, 0.0], [0.5, 0.5, 0.5]];
+    /// let types = [11, 17];
+    /// let cell = Cell::new(&lattice, &positions, &types);
+    /// let (refined, mapping) = cell.refined(1.0e-6).unwrap();
+    /// assert_eq!(refined.positions.len(), 8);
+    /// assert_eq!(refined.types[mapping[0]], 11);
+    /// assert_eq!(refined.types[mapping[1]], 17);
+    /// ```
+    pub fn refined(&self, tolerance: impl Into<Tolerance>) -> Result<(Cell, Vec<usize>), Error> {
+        let tolerance = tolerance.into();
+        self.check("spgat_refine_cell", tolerance)?;
+        let n_atoms = self.positions.len();
+        // conventional cells of centered lattices hold up to 4 times as many atoms
+        let (mut lattice, mut positions, mut types) = self.buffers(4 * n_atoms);
+        let res = unsafe {
+            ffi::spgat_refine_cell(
+                lattice.as_mut_ptr(),
+                positions.as_mut_ptr(),
+                types.as_mut_ptr(),
+                n_atoms as c_int,
+                tolerance.symprec,
+                tolerance.raw_angle_tolerance(),
+            )
+        };
+        if res == 0 {
+            return Err(Error::last("spgat_refine_cell").with_inputs(n_atoms, tolerance));
+        }
+        positions.truncate(res as usize);
+        types.truncate(res as usize);
+        let cell = Cell {
+            lattice,
+            positions,
+            types,
+        };
+        let mapping = self.mapping_to(&cell, true, "spgat_refine_cell", tolerance)?;
+        Ok((cell, mapping))
+    }
+
+    // Copies the cell into buffers with room for `capacity` atoms.
+    fn buffers(&self, capacity: usize) -> ([[f64; 3]; 3], Vec<[f64; 3]>, Vec<c_int>) {
+        let mut positions = self.positions.clone();
+        positions.resize(capacity, [0.0; 3]);
+        let mut types = self.types.clone();
+        types.resize(capacity, 0);
+        (self.lattice, positions, types)
+    }
+
+    // Maps each atom of this cell to the first atom of `other` in the same orbit of the primitive
+    // lattice translations. An idealized `other` is built from the standardized cell of the
+    // dataset and shares its frame, otherwise it shares the frame of this cell. Each atom of
+    // `other` is matched by position to a site of that frame, which tells its primitive atom.
+    // Callers check this cell before.
+    fn mapping_to(
+        &self,
+        other: &Cell,
+        idealized: bool,
+        function: &'static str,
+        tolerance: Tolerance,
+    ) -> Result<Vec<usize>, Error> {
+        let dataset = Dataset::new_unchecked(self, tolerance)?;
+        let n_atoms = self.positions.len();
+        let mismatch = |message: String| {
+            Error::new(SpglibError::AtomMappingFailed, function)
+                .with_message(message)
+                .with_inputs(n_atoms, tolerance)
+        };
+        // every atom of the crystal is a site of this cell or of the standardized cell, up to a
+        // translation by its lattice
+        let (lattice, sites, types, primitive_indices) = if idealized {
+            (
+                dataset.std_lattice,
+                &dataset.std_positions,
+                &dataset.std_types,
+                &dataset.std_mapping_to_primitive,
+            )
+        } else {
+            (
+                self.lattice,
+                &self.positions,
+                &self.types,
+                &dataset.mapping_to_primitive,
+            )
+        };
+        let inverse_lattice =
+            inverse(&lattice).ok_or_else(|| mismatch("the lattice is singular".to_string()))?;
+        let other_indices = other
+            .positions
+            .iter()
+            .zip(other.types.iter())
+            .enumerate()
+            .map(|(k, (position, t))| {
+                // fractional coordinates in the lattice of the sites
+                let r = mat_vec(&inverse_lattice, &mat_vec(&other.lattice, position));
+                sites
+                    .iter()
+                    .zip(types.iter())
+                    .position(|(q, u)| {
+                        // difference reduced to the nearest lattice translation
+                        let mut f = [r[0] - q[0], r[1] - q[1], r[2] - q[2]];
+                        for x in f.iter_mut() {
+                            *x -= x.round();
+                        }
+                        let d = mat_vec(&lattice, &f);
+                        t == u && dot(&d, &d).sqrt() < tolerance.symprec
+                    })
+                    .map(|j| primitive_indices[j])
+                    .ok_or_else(|| mismatch(format!("atom {} of the result matches no atom", k)))
+            })
+            .collect::<Result<Vec<i32>, Error>>()?;
+        dataset
+            .mapping_to_primitive
+            .iter()
+            .enumerate()
+            .map(|(i, index)| {
+                other_indices
+                    .iter()
+                    .position(|other_index| other_index == index)
+                    .ok_or_else(|| mismatch(format!("atom {} matches no atom of the result", i)))
+            })
+            .collect()
+    }
+
+    /// Returns the space group operations of the cell.
+    /// Refer to the full documentation of the implementation [here](https://spglib.github.io/spglib/api.html#spg-get-symmetry).
+    ///
+    /// # Example
+    ///
+    /// Get the symmetry operations of a BCC cell.
+    ///
+    /// ```
+    /// use spglib::cell::Cell;
+    ///
+    /// let lattice = [
+    ///     [4.0, 0.0, 0.0],
+    ///     [0.0, 4.0, 0.0],
+    ///     [0.0, 0.0, 4.0]
+    /// ];
+    /// let positions = [
+    ///     [0.0, 0.0, 0.0],
+    ///     [0.5, 0.5, 0.5]
+    /// ];
+    /// let types = [1, 1];
+    /// let cell = Cell::new(&lattice, &positions, &types);
+    /// let symmetry = cell.symmetry_operations(1.0e-6).unwrap();
+    /// assert_eq!(symmetry.rotations.len(), 96);
+    /// assert_eq!(symmetry.permutations[0], vec![0, 1]);
+    /// ```
+    pub fn symmetry_operations(&self, tolerance: impl Into<Tolerance>) -> Result<Symmetry, Error> {
+        let tolerance = tolerance.into();
+        self.check("spgat_get_symmetry", tolerance)?;
+        let (rotations, translations) = self.operations(tolerance)?;
+        let permutations = rotations
+            .iter()
+            .zip(translations.iter())
+            .map(|(rotation, translation)| self.permutation(rotation, translation))
+            .collect();
+        Ok(Symmetry {
+            rotations,
+            translations,
+            permutations,
+        })
+    }
+
+    /// Returns the magnetic symmetry of the cell with a collinear spin on each atom.
+    /// Refer to the full documentation of the implementation [here](https://spglib.github.io/spglib/api.html#spg-get-symmetry-with-collinear-spin).
+    ///
+    /// Fails with [`CellError::SpinCountMismatch`] if the number of spins differs from the number
+    /// of atoms, and with [`CellError::NonFinite`] if a spin is not finite.
+    ///
+    /// # Example
+    ///
+    /// Get the magnetic symmetry of an antiferromagnetic CsCl-type cell.
+    ///
+    /// ```
+    /// use spglib::cell::Cell;
+    ///
+    /// let lattice = [
+    ///     [4.0, 0.0, 0.0],
+    ///     [0.0, 4.0, 0.0],
+    ///     [0.0, 0.0, 4.0]
+    /// ];
+    /// let positions = [
+    ///     [0.0, 0.0, 0.0],
+    ///     [0.5, 0.5, 0.5]
+    /// ];
+    /// let types = [1, 1];
+    /// let cell = Cell::new(&lattice, &positions, &types);
+    /// let spin_symmetry = cell.symmetry_with_collinear_spin(&[1.0, -1.0], 1.0e-6).unwrap();
+    /// assert_eq!(spin_symmetry.symmetry.rotations.len(), 96);
+    /// assert_eq!(spin_symmetry.spin_flips.iter().filter(|&&s| s == -1).count(), 48);
+    /// ```
+    pub fn symmetry_with_collinear_spin(
+        &self,
+        spins: &[f64],
+        tolerance: impl Into<Tolerance>,
+    ) -> Result<SpinSymmetry, Error> {
+        let tolerance = tolerance.into();
+        let function = "spgat_get_symmetry_with_site_tensors";
+        self.check(function, tolerance)?;
+        let n_atoms = self.positions.len();
+        let invalid = |err| Error::invalid_cell(err, function).with_inputs(n_atoms, tolerance);
+        if spins.len() != n_atoms {
+            return Err(invalid(CellError::SpinCountMismatch {
+                atoms: n_atoms,
+                spins: spins.len(),
+            }));
+        }
+        if !spins.iter().all(|spin| spin.is_finite()) {
+            return Err(invalid(CellError::NonFinite));
+        }
+        // spglib keeps those of the operations without spins that preserve the spins
+        let (mut rotations, mut translations) = self.operations(tolerance)?;
+        let n_operations = rotations.len();
+        let mut equivalent_atoms = vec![0; n_atoms];
+        let mut primitive_lattice = [[0.0; 3]; 3];
+        let mut spin_flips = vec![0; n_operations];
         let res = unsafe {
-            ffi::spg_standardize_cell(
+            ffi::spgat_get_symmetry_with_site_tensors(
+                rotations.as_mut_ptr(),
+                translations.as_mut_ptr(),
+                equivalent_atoms.as_mut_ptr(),
+                primitive_lattice.as_mut_ptr(),
+                spin_flips.as_mut_ptr(),
+                n_operations as c_int,
                 self.lattice.as_ptr() as *mut [f64; 3],
                 self.positions.as_ptr() as *mut [f64; 3],
-                self.types.as_ptr() as *mut c_int,
-                self.positions.len() as c_int,
-                to_primitive,
-                no_idealize,
-                symprec,
+                self.types.as_ptr(),
+                spins.as_ptr(),
+                0,
+                n_atoms as c_int,
+                1,
+                tolerance.symprec,
+                tolerance.raw_angle_tolerance(),
             )
         };
         if res == 0 {
-            return Err(SpglibError::CellStandardizationFailed);
+            return Err(Error::last(function).with_inputs(n_atoms, tolerance));
         }
-        Ok(())
+        rotations.truncate(res as usize);
+        translations.truncate(res as usize);
+        spin_flips.truncate(res as usize);
+        let permutations = rotations
+            .iter()
+            .zip(translations.iter())
+            .map(|(rotation, translation)| self.permutation(rotation, translation))
+            .collect();
+        Ok(SpinSymmetry {
+            symmetry: Symmetry {
+                rotations,
+                translations,
+                permutations,
+            },
+            spin_flips,
+            equivalent_atoms,
+        })
+    }
+
+    /// Returns the space group in short Hermann-Mauguin notation without computing the full dataset.
+    /// Refer to the full documentation of the implementation [here](https://spglib.github.io/spglib/api.html#spg-get-international).
+    ///
+    /// # Example
+    ///
+    /// Get the international symbol of a BCC cell.
+    ///
+    /// ```
+    /// use spglib::cell::Cell;
+    ///
+    /// let lattice = [
+    ///     [4.0, 0.0, 0.0],
+    ///     [0.0, 4.0, 0.0],
+    ///     [0.0, 0.0, 4.0]
+    /// ];
+    /// let positions = [
+    ///     [0.0, 0.0, 0.0],
+    ///     [0.5, 0.5, 0.5]
+    /// ];
+    /// let types = [1, 1];
+    /// let cell = Cell::new(&lattice, &positions, &types);
+    /// let symbol = cell.international_symbol(1.0e-6).unwrap();
+    /// assert_eq!(symbol.number, 229);
+    /// assert_eq!(symbol.symbol, "Im-3m");
+    /// ```
+    pub fn international_symbol(
+        &self,
+        tolerance: impl Into<Tolerance>,
+    ) -> Result<SpacegroupSymbol, Error> {
+        let tolerance = tolerance.into();
+        let n_atoms = self.positions.len();
+        let function = "spgat_get_international";
+        self.check(function, tolerance)?;
+        let mut symbol: [c_char; 11] = [0; 11];
+        let number = unsafe {
+            ffi::spgat_get_international(
+                symbol.as_mut_ptr(),
+                self.lattice.as_ptr() as *mut [f64; 3],
+                self.positions.as_ptr() as *mut [f64; 3],
+                self.types.as_ptr(),
+                n_atoms as c_int,
+                tolerance.symprec,
+                tolerance.raw_angle_tolerance(),
+            )
+        };
+        if number == 0 {
+            return Err(Error::last(function).with_inputs(n_atoms, tolerance));
+        }
+        let symbol = match CString::from(unsafe { CStr::from_ptr(symbol.as_ptr()) }).to_str() {
+            Ok(s) => String::from(s),
+            Err(_) => {
+                return Err(
+                    Error::new(SpglibError::Unknown, function).with_inputs(n_atoms, tolerance)
+                )
+            }
+        };
+        Ok(SpacegroupSymbol { number, symbol })
+    }
+
+    /// Returns the space group in Schoenflies notation without computing the full dataset.
+    /// Refer to the full documentation of the implementation [here](https://spglib.github.io/spglib/api.html#spg-get-schoenflies).
+    ///
+    /// # Example
+    ///
+    /// Get the Schoenflies symbol of a BCC cell.
+    ///
+    /// ```
+    /// use spglib::cell::Cell;
+    ///
+    /// let lattice = [
+    ///     [4.0, 0.0, 0.0],
+    ///     [0.0, 4.0, 0.0],
+    ///     [0.0, 0.0, 4.0]
+    /// ];
+    /// let positions = [
+    ///     [0.0, 0.0, 0.0],
+    ///     [0.5, 0.5, 0.5]
+    /// ];
+    /// let types = [1, 1];
+    /// let cell = Cell::new(&lattice, &positions, &types);
+    /// let symbol = cell.schoenflies_symbol(1.0e-6).unwrap();
+    /// assert_eq!(symbol.number, 229);
+    /// assert_eq!(symbol.symbol, "Oh^9");
+    /// ```
+    pub fn schoenflies_symbol(
+        &self,
+        tolerance: impl Into<Tolerance>,
+    ) -> Result<SpacegroupSymbol, Error> {
+        let tolerance = tolerance.into();
+        let n_atoms = self.positions.len();
+        let function = "spgat_get_schoenflies";
+        self.check(function, tolerance)?;
+        let mut symbol: [c_char; 7] = [0; 7];
+        let number = unsafe {
+            ffi::spgat_get_schoenflies(
+                symbol.as_mut_ptr(),
+                self.lattice.as_ptr() as *mut [f64; 3],
+                self.positions.as_ptr() as *mut [f64; 3],
+                self.types.as_ptr(),
+                n_atoms as c_int,
+                tolerance.symprec,
+                tolerance.raw_angle_tolerance(),
+            )
+        };
+        if number == 0 {
+            return Err(Error::last(function).with_inputs(n_atoms, tolerance));
+        }
+        let symbol = match CString::from(unsafe { CStr::from_ptr(symbol.as_ptr()) }).to_str() {
+            Ok(s) => String::from(s),
+            Err(_) => {
+                return Err(
+                    Error::new(SpglibError::Unknown, function).with_inputs(n_atoms, tolerance)
+                )
+            }
+        };
+        Ok(SpacegroupSymbol { number, symbol })
+    }
+
+    // Returns the number of symmetry operations of the cell.
+    fn multiplicity(&self, tolerance: Tolerance) -> Result<c_int, Error> {
+        let n_atoms = self.positions.len();
+        let multiplicity = unsafe {
+            ffi::spgat_get_multiplicity(
+                self.lattice.as_ptr() as *mut [f64; 3],
+                self.positions.as_ptr() as *mut [f64; 3],
+                self.types.as_ptr(),
+                n_atoms as c_int,
+                tolerance.symprec,
+                tolerance.raw_angle_tolerance(),
+            )
+        };
+        if multiplicity == 0 {
+            return Err(Error::last("spgat_get_multiplicity").with_inputs(n_atoms, tolerance));
+        }
+        Ok(multiplicity)
+    }
+
+    // Returns the rotations and translations of the space group operations of a checked cell.
+    fn operations(&self, tolerance: Tolerance) -> Result<Operations, Error> {
+        let n_atoms = self.positions.len();
+        let multiplicity = self.multiplicity(tolerance)?;
+        let mut rotations = vec![[[0; 3]; 3]; multiplicity as usize];
+        let mut translations = vec![[0.0; 3]; multiplicity as usize];
+        let res = unsafe {
+            ffi::spgat_get_symmetry(
+                rotations.as_mut_ptr(),
+                translations.as_mut_ptr(),
+                multiplicity,
+                self.lattice.as_ptr() as *mut [f64; 3],
+                self.positions.as_ptr() as *mut [f64; 3],
+                self.types.as_ptr(),
+                n_atoms as c_int,
+                tolerance.symprec,
+                tolerance.raw_angle_tolerance(),
+            )
+        };
+        if res == 0 {
+            return Err(Error::last("spgat_get_symmetry").with_inputs(n_atoms, tolerance));
+        }
+        rotations.truncate(res as usize);
+        translations.truncate(res as usize);
+        Ok((rotations, translations))
+    }
+
+    // Returns the atom permutation of a symmetry operation.
+    // Each image is assigned to the nearest atom of the same type under periodic boundary conditions.
+    fn permutation(&self, rotation: &[[i32; 3]; 3], translation: &[f64; 3]) -> Vec<usize> {
+        self.positions
+            .iter()
+            .zip(self.types.iter())
+            .enumerate()
+            .map(|(i, (position, t))| {
+                let mut image = *translation;
+                for (row, x) in rotation.iter().zip(image.iter_mut()) {
+                    for (r, p) in row.iter().zip(position.iter()) {
+                        *x += *r as f64 * p;
+                    }
+                }
+                let distance = |other: &[f64; 3]| {
+                    let mut f = [
+                        image[0] - other[0],
+                        image[1] - other[1],
+                        image[2] - other[2],
+                    ];
+                    for x in f.iter_mut() {
+                        *x -= x.round();
+                    }
+                    let d = mat_vec(&self.lattice, &f);
+                    d[0] * d[0] + d[1] * d[1] + d[2] * d[2]
+                };
+                self.positions
+                    .iter()
+                    .enumerate()
+                    .filter(|(j, _)| self.types[*j] == *t)
+                    .map(|(j, other)| (j, distance(other)))
+                    .min_by(|a, b| a.1.total_cmp(&b.1))
+                    .map_or(i, |(j, _)| j)
+            })
+            .collect()
     }
 
     /// Applies a Delaunay reduction to the cell.
     /// Refer to the full documentation of the implementation [here](https://spglib.github.io/spglib/api.html#spg-delaunay-reduce).
-    pub fn delaunay_reduce(&mut self, eps: f64) -> Result<(), SpglibError> {
+    pub fn delaunay_reduce(&mut self, eps: f64) -> Result<(), Error> {
+        validate_lattice(&self.lattice)
+            .map_err(|err| Error::invalid_cell(err, "spg_delaunay_reduce").with_symprec(eps))?;
         let res = unsafe { ffi::spg_delaunay_reduce(self.lattice.as_ptr() as *mut [f64; 3], eps) };
         if res == 0 {
-            return Err(SpglibError::DelaunayFailed);
+            return Err(Error::last("spg_delaunay_reduce").with_symprec(eps));
         }
         Ok(())
     }
 
     /// Applies a Niggli reduction to the cell.
     /// Refer to the full documentation of the implementation [here](https://spglib.github.io/spglib/api.html#spg-niggli-reduce).
-    pub fn niggli_reduce(&mut self, eps: f64) -> Result<(), SpglibError> {
+    pub fn niggli_reduce(&mut self, eps: f64) -> Result<(), Error> {
+        validate_lattice(&self.lattice)
+            .map_err(|err| Error::invalid_cell(err, "spg_niggli_reduce").with_symprec(eps))?;
         let res = unsafe { ffi::spg_niggli_reduce(self.lattice.as_ptr() as *mut [f64; 3], eps) };
         if res == 0 {
-            return Err(SpglibError::NiggliFailed);
+            return Err(Error::last("spg_niggli_reduce").with_symprec(eps));
         }
         Ok(())
     }
 
-    /// This method is not yet implemented.
+    /// Returns the irreducible points of a uniform reciprocal-space mesh.
+    /// Refer to the full documentation of the implementation [here](https://spglib.github.io/spglib/api.html#spg-get-ir-reciprocal-mesh).
+    ///
+    /// Each element of `shift` moves the mesh by half a grid spacing along that axis.
+    /// When `is_time_reversal` is set, `k` and `-k` are treated as equivalent.
+    ///
+    /// # Example
+    ///
+    /// Reduce a 4x4x4 mesh of a simple cubic cell.
+    ///
+    /// ```
+    /// use spglib::cell::Cell;
+    ///
+    /// let lattice = [[4., 0., 0.], [0., 4., 0.], [0., 0., 4.]];
+    /// let cell = Cell::new(&lattice, &[[0., 0., 0.]], &[1]);
+    /// let mesh = cell
+    ///     .ir_reciprocal_mesh((4, 4, 4), (false, false, false), true, 1e-5)
+    ///     .unwrap();
+    /// assert_eq!(mesh.grid_address.len(), 64);
+    /// assert_eq!(mesh.ir_grid_points.len(), 10);
+    /// assert_eq!(mesh.weights.iter().sum::<usize>(), 64);
+    /// ```
+    ///
+    /// Fails with [`SpglibError::InvalidMesh`] if any mesh dimension is not positive or the mesh
+    /// has more than 2^24 points.
     pub fn ir_reciprocal_mesh(
         &self,
         mesh: (i32, i32, i32),
         shift: (bool, bool, bool),
-    ) -> Result<(), SpglibError> {
-        unimplemented!()
+        is_time_reversal: bool,
+        symprec: f64,
+    ) -> Result<ReciprocalMesh, Error> {
+        // spglib has no angle tolerance for this search
+        let tolerance = Tolerance::new(symprec);
+        let function = "spg_get_ir_reciprocal_mesh";
+        let mesh = [mesh.0, mesh.1, mesh.2];
+        let is_shift = [shift.0, shift.1, shift.2];
+        let n_atoms = self.positions.len();
+        let n_points = mesh_size(&mesh).ok_or_else(|| {
+            Error::new(SpglibError::InvalidMesh(mesh), function).with_inputs(n_atoms, tolerance)
+        })?;
+        self.check(function, tolerance)?;
+        let raw_shift = is_shift.map(|s| s as c_int);
+        let mut grid_address = vec![[0; 3]; n_points];
+        let mut mapping = vec![0; n_points];
+        let res = unsafe {
+            ffi::spg_get_ir_reciprocal_mesh(
+                grid_address.as_mut_ptr(),
+                mapping.as_mut_ptr(),
+                mesh.as_ptr(),
+                raw_shift.as_ptr(),
+                is_time_reversal as c_int,
+                self.lattice.as_ptr() as *mut [f64; 3],
+                self.positions.as_ptr() as *mut [f64; 3],
+                self.types.as_ptr(),
+                n_atoms as c_int,
+                symprec,
+            )
+        };
+        if res == 0 {
+            return Err(Error::last(function).with_inputs(n_atoms, tolerance));
+        }
+        Ok(ReciprocalMesh::from_raw(
+            mesh,
+            is_shift,
+            grid_address,
+            mapping,
+        ))
+    }
+}
+
+// Checks that a lattice is finite, non-degenerate and right-handed.
+pub(crate) fn validate_lattice(lattice: &[[f64; 3]; 3]) -> Result<(), CellError> {
+    if !lattice.iter().flatten().all(|x| x.is_finite()) {
+        return Err(CellError::NonFinite);
+    }
+    let volume = det(lattice);
+    let lengths: f64 = (0..3)
+        .map(|j| {
+            let v = column(lattice, j);
+            dot(&v, &v).sqrt()
+        })
+        .product();
+    if volume.abs() <= DEGENERACY_TOLERANCE * lengths || lengths == 0.0 {
+        return Err(CellError::DegenerateLattice { volume });
+    }
+    if volume < 0.0 {
+        return Err(CellError::LeftHandedLattice { volume });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cell::Cell;
+    use crate::fixtures::rutile;
+
+    fn fcc_primitive() -> Cell {
+        let lattice = [[0., 2., 2.], [2., 0., 2.], [2., 2., 0.]];
+        Cell::new(&lattice, &[[0., 0., 0.]], &[1])
+    }
+
+    fn fcc_conventional() -> Cell {
+        let lattice = [[4., 0., 0.], [0., 4., 0.], [0., 0., 4.]];
+        let positions = [[0., 0., 0.], [0., 0.5, 0.5], [0.5, 0., 0.5], [0.5, 0.5, 0.]];
+        Cell::new(&lattice, &positions, &[1, 1, 1, 1])
+    }
+
+    #[test]
+    fn standardize_grows_cell() {
+        let mut cell = fcc_primitive();
+        cell.standardize(false, false, 1e-5).unwrap();
+        assert_eq!(cell.positions.len(), 4);
+        assert_eq!(cell.types.len(), 4);
+        assert_eq!(cell.lattice, [[4., 0., 0.], [0., 4., 0.], [0., 0., 4.]]);
+    }
+
+    #[test]
+    fn standardized_no_idealize() {
+        let cell = fcc_conventional();
+        let (primitive, mapping) = cell.standardized(true, true, 1e-5).unwrap();
+        assert_eq!(primitive.positions.len(), 1);
+        assert_eq!(mapping, vec![0, 0, 0, 0]);
+        let (conventional, mapping) = fcc_primitive().standardized(false, true, 1e-5).unwrap();
+        assert_eq!(conventional.positions.len(), 4);
+        assert_eq!(mapping, vec![0]);
+    }
+
+    #[test]
+    fn symmetry_operations_permute_atoms() {
+        let cell = rutile();
+        let types = &cell.types;
+        let symmetry = cell.symmetry_operations(1e-5).unwrap();
+        assert_eq!(symmetry.rotations.len(), 16);
+        assert_eq!(symmetry.translations.len(), 16);
+        for permutation in symmetry.permutations.iter() {
+            let mut sorted = permutation.clone();
+            sorted.sort_unstable();
+            assert_eq!(sorted, vec![0, 1, 2, 3, 4, 5]);
+            for (i, j) in permutation.iter().enumerate() {
+                assert_eq!(types[i], types[*j]);
+            }
+        }
+        // the 4_2 screw axis exchanges the two titanium sites
+        assert!(symmetry.permutations.iter().any(|p| p[0] == 1));
+    }
+
+    #[test]
+    fn symmetry_with_collinear_spin() {
+        use crate::error::{CellError, SpglibError};
+
+        let lattice = [[4., 0., 0.], [0., 4., 0.], [0., 0., 4.]];
+        let positions = [[0., 0., 0.], [0.5, 0.5, 0.5]];
+        let cell = Cell::new(&lattice, &positions, &[1, 1]);
+
+        let ferro = cell.symmetry_with_collinear_spin(&[1., 1.], 1e-5).unwrap();
+        assert_eq!(ferro.symmetry.rotations.len(), 96);
+        assert!(ferro.spin_flips.iter().all(|&s| s == 1));
+        assert_eq!(ferro.equivalent_atoms, vec![0, 0]);
+
+        // a moment on only one site breaks the body centering
+        let ferri = cell.symmetry_with_collinear_spin(&[1., 0.], 1e-5).unwrap();
+        assert_eq!(ferri.symmetry.rotations.len(), 48);
+        assert!(ferri.spin_flips.iter().all(|&s| s == 1));
+        assert_eq!(ferri.equivalent_atoms, vec![0, 1]);
+        for permutation in ferri.symmetry.permutations.iter() {
+            assert_eq!(permutation, &vec![0, 1]);
+        }
+
+        let anti = cell.symmetry_with_collinear_spin(&[2., -2.], 1e-5).unwrap();
+        assert_eq!(anti.symmetry.rotations.len(), 96);
+        assert_eq!(anti.spin_flips.iter().filter(|&&s| s == -1).count(), 48);
+
+        let err = cell.symmetry_with_collinear_spin(&[1.], 1e-5).unwrap_err();
+        assert!(matches!(
+            err.kind,
+            SpglibError::InvalidCell(CellError::SpinCountMismatch { atoms: 2, spins: 1 })
+        ));
+        let err = cell
+            .symmetry_with_collinear_spin(&[1., f64::NAN], 1e-5)
+            .unwrap_err();
+        assert!(matches!(
+            err.kind,
+            SpglibError::InvalidCell(CellError::NonFinite)
+        ));
+    }
+
+    #[test]
+    fn symbols_of_rutile() {
+        let cell = rutile();
+        let international = cell.international_symbol(1e-5).unwrap();
+        assert_eq!(international.number, 136);
+        assert_eq!(international.symbol, "P4_2/mnm");
+        let schoenflies = cell.schoenflies_symbol(1e-5).unwrap();
+        assert_eq!(schoenflies.number, 136);
+        assert_eq!(schoenflies.symbol, "D4h^14");
+    }
+
+    #[test]
+    fn primitive_of_supercell() {
+        // conventional rock salt cell
+        let lattice = [[5.6, 0., 0.], [0., 5.6, 0.], [0., 0., 5.6]];
+        let mut positions = Vec::new();
+        let mut types = Vec::new();
+        for (t, origin) in [(11, [0., 0., 0.]), (17, [0.5, 0., 0.])].iter() {
+            for shift in [[0., 0., 0.], [0., 0.5, 0.5], [0.5, 0., 0.5], [0.5, 0.5, 0.]].iter() {
+                positions.push([
+                    (origin[0] + shift[0]) % 1.,
+                    (origin[1] + shift[1]) % 1.,
+                    (origin[2] + shift[2]) % 1.,
+                ]);
+                types.push(*t);
+            }
+        }
+        let cell = Cell::new(&lattice, &positions, &types);
+        let (primitive, mapping) = cell.primitive(1e-5).unwrap();
+        assert_eq!(primitive.positions.len(), 2);
+        for (i, t) in types.iter().enumerate() {
+            assert_eq!(primitive.types[mapping[i]], *t);
+        }
+        let (refined, mapping) = cell.refined(1e-5).unwrap();
+        assert_eq!(refined.positions.len(), 8);
+        for (i, t) in types.iter().enumerate() {
+            assert_eq!(refined.types[mapping[i]], *t);
+        }
+    }
+
+    #[test]
+    fn mapping_of_centered_cell() {
+        use crate::dataset::Dataset;
+
+        // body-centered tetragonal cell with three orbits, rotated, shifted and shuffled
+        let (cos, sin) = (0.5f64, 0.75f64.sqrt());
+        let lattice = [
+            [4. * cos, -4. * sin, 0.],
+            [4. * sin, 4. * cos, 0.],
+            [0., 0., 6.],
+        ];
+        let sites = [
+            (1, [0., 0., 0.]),
+            (2, [0., 0., 0.3]),
+            (2, [0., 0., 0.7]),
+            (3, [0.5, 0., 0.25]),
+            (3, [0., 0.5, 0.25]),
+        ];
+        let mut positions = Vec::new();
+        let mut types = Vec::new();
+        for &centering in [0., 0.5].iter() {
+            for &(t, p) in sites.iter().rev() {
+                positions.push([
+                    0.1 + p[0] + centering,
+                    0.2 + p[1] + centering,
+                    p[2] + centering,
+                ]);
+                types.push(t);
+            }
+        }
+        let cell = Cell::new(&lattice, &positions, &types);
+        let dataset = Dataset::new(&cell, 1e-5).unwrap();
+        assert_eq!(dataset.spacegroup_number, 139);
+        let results = [
+            cell.primitive(1e-5).unwrap(),
+            cell.refined(1e-5).unwrap(),
+            cell.standardized(true, false, 1e-5).unwrap(),
+            cell.standardized(true, true, 1e-5).unwrap(),
+            cell.standardized(false, false, 1e-5).unwrap(),
+            cell.standardized(false, true, 1e-5).unwrap(),
+        ];
+        for (other, mapping) in results.iter() {
+            assert_eq!(mapping.len(), 10);
+            for i in 0..10 {
+                assert_eq!(other.types[mapping[i]], types[i]);
+                for j in 0..10 {
+                    let same = dataset.mapping_to_primitive[i] == dataset.mapping_to_primitive[j];
+                    assert_eq!(mapping[i] == mapping[j], same);
+                }
+            }
+        }
+        assert_eq!(results[0].0.positions.len(), 5);
+    }
+
+    #[test]
+    fn ir_reciprocal_mesh_cubic() {
+        use crate::error::SpglibError;
+
+        let lattice = [[4., 0., 0.], [0., 4., 0.], [0., 0., 4.]];
+        let cell = Cell::new(&lattice, &[[0., 0., 0.]], &[1]);
+        let mesh = cell
+            .ir_reciprocal_mesh((4, 4, 4), (false, false, false), true, 1e-5)
+            .unwrap();
+        // stars of (0,0,0), (0,0,1), (0,0,2), (0,1,1), (0,1,2), (0,2,2), (1,1,1), (1,1,2), (1,2,2), (2,2,2)
+        let mut weights = mesh.weights.clone();
+        weights.sort_unstable();
+        assert_eq!(weights, vec![1, 1, 3, 3, 6, 6, 8, 12, 12, 12]);
+        for (i, &ir) in mesh.mapping.iter().enumerate() {
+            assert_eq!(mesh.mapping[ir], ir);
+            assert!(ir <= i);
+        }
+        // all eight points (+-1/4, +-1/4, +-1/4) of the shifted 2x2x2 mesh are equivalent
+        let mesh = cell
+            .ir_reciprocal_mesh((2, 2, 2), (true, true, true), true, 1e-5)
+            .unwrap();
+        assert_eq!(mesh.weights, vec![8]);
+        assert_eq!(mesh.ir_qpoints(), vec![[0.25, 0.25, 0.25]]);
+        // invalid meshes never reach spglib
+        for &mesh in [(4, 0, 4), (4, -4, 4), (65536, 65536, 2), (1000, 1000, 1000)].iter() {
+            let err = cell
+                .ir_reciprocal_mesh(mesh, (false, false, false), true, 1e-5)
+                .unwrap_err();
+            assert!(matches!(err.kind, SpglibError::InvalidMesh(_)));
+            assert_eq!(err.angle_tolerance, None);
+        }
+    }
+
+    #[test]
+    fn ir_reciprocal_mesh_hexagonal() {
+        let a = 3.0;
+        let lattice = [
+            [a, -a / 2., 0.],
+            [0., a * 3f64.sqrt() / 2., 0.],
+            [0., 0., 5.],
+        ];
+        let cell = Cell::new(&lattice, &[[0., 0., 0.]], &[1]);
+        // in-plane: Gamma, the two K points and the six remaining points; along c: 0 and +-1/3
+        let mesh = cell
+            .ir_reciprocal_mesh((3, 3, 3), (false, false, false), true, 1e-5)
+            .unwrap();
+        let mut weights = mesh.weights.clone();
+        weights.sort_unstable();
+        assert_eq!(weights, vec![1, 2, 2, 4, 6, 12]);
+    }
+
+    #[test]
+    fn ir_reciprocal_mesh_time_reversal() {
+        let lattice = [[4., 0.3, 0.2], [0., 5., 0.4], [0., 0., 6.]];
+        let positions = [[0., 0., 0.], [0.3, 0.2, 0.1], [0.6, 0.7, 0.45]];
+        let cell = Cell::new(&lattice, &positions, &[1, 2, 3]);
+        // P1: only k and -k are equivalent, and only with time reversal
+        let mesh = cell
+            .ir_reciprocal_mesh((3, 3, 3), (false, false, false), true, 1e-5)
+            .unwrap();
+        assert_eq!(mesh.ir_grid_points.len(), 14);
+        let mesh = cell
+            .ir_reciprocal_mesh((3, 3, 3), (false, false, false), false, 1e-5)
+            .unwrap();
+        assert_eq!(mesh.ir_grid_points.len(), 27);
+        assert!(mesh.weights.iter().all(|&w| w == 1));
+    }
+
+    #[test]
+    fn lattice_geometry() {
+        let cell = fcc_primitive();
+        assert_eq!(cell.volume(), 16.0);
+        assert_eq!(
+            cell.metric_tensor(),
+            [[8., 4., 4.], [4., 8., 4.], [4., 4., 8.]]
+        );
+        let parameters = cell.lattice_parameters();
+        assert!((parameters.alpha - 60.0).abs() < 1e-12);
+        assert!((parameters.beta - 60.0).abs() < 1e-12);
+        // lattice and reciprocal vectors are dual to each other
+        let reciprocal = cell.reciprocal_lattice().unwrap();
+        let reciprocal_2pi = cell.reciprocal_lattice_2pi().unwrap();
+        for i in 0..3 {
+            for j in 0..3 {
+                let product: f64 = (0..3).map(|k| cell.lattice[k][i] * reciprocal[k][j]).sum();
+                assert!((product - (i == j) as i32 as f64).abs() < 1e-12);
+                let ratio = reciprocal_2pi[i][j] - 2.0 * std::f64::consts::PI * reciprocal[i][j];
+                assert!(ratio.abs() < 1e-12);
+            }
+        }
+        let rebuilt =
+            Cell::from_lattice_parameters(&parameters, &cell.positions, &cell.types).unwrap();
+        assert!((rebuilt.volume() - 16.0).abs() < 1e-10);
+        assert_eq!(rebuilt.lattice[1][0], 0.0);
+        let flat = Cell::new(&[[1., 2., 0.], [0., 0., 0.], [0., 0., 1.]], &[], &[]);
+        assert!(flat.reciprocal_lattice().is_none());
+    }
+
+    #[test]
+    fn cartesian_and_periodic_geometry() {
+        let lattice = [[3., -1.5, 0.], [0., 2.598076211353316, 0.], [0., 0., 5.]];
+        let cartesian = [[0., 0., 0.], [1.5, 0.8660254037844386, 2.5], [-7., 20., 9.]];
+        let mut cell = Cell::from_cartesian(&lattice, &cartesian, &[1, 1, 2]).unwrap();
+        for (r, expected) in cell.cartesian_positions().iter().zip(cartesian.iter()) {
+            assert!((0..3).all(|k| (r[k] - expected[k]).abs() < 1e-10));
+        }
+        cell.wrap_positions(1e-8);
+        assert!(cell
+            .positions
+            .iter()
+            .flatten()
+            .all(|x| (0.0..1.0).contains(x)));
+        // compare with a brute-force search over neighbouring cells in a skewed supercell
+        let skewed = [[6., 5.5, 0.], [0., 1., 0.], [0., 0., 4.]];
+        let cell = Cell::new(&skewed, &[[0.1, 0.2, 0.], [0.7, 0.9, 0.6]], &[1, 1]);
+        let mut brute = f64::INFINITY;
+        for n0 in -5..=5 {
+            for n1 in -5..=5 {
+                for n2 in -5..=5 {
+                    let x = [0.6 + n0 as f64, 0.7 + n1 as f64, 0.6 + n2 as f64];
+                    let r: f64 = (0..3)
+                        .map(|i| (0..3).map(|j| skewed[i][j] * x[j]).sum::<f64>().powi(2))
+                        .sum();
+                    brute = brute.min(r.sqrt());
+                }
+            }
+        }
+        assert!((cell.distance(0, 1) - brute).abs() < 1e-12);
+        assert!((cell.distance(1, 0) - brute).abs() < 1e-12);
+        assert_eq!(cell.distance(1, 1), 0.0);
+        assert!(Cell::from_cartesian(&[[0.; 3]; 3], &cartesian, &[1, 1, 2]).is_none());
+    }
+
+    #[test]
+    fn validation() {
+        use crate::dataset::Dataset;
+        use crate::error::{CellError, SpglibError};
+
+        let lattice = [[4., 0., 0.], [0., 4., 0.], [0., 0., 4.]];
+        let positions = [[0., 0., 0.], [0.5, 0.5, 0.5]];
+        assert!(Cell::try_new(&lattice, &positions, &[1, 1], 1e-5).is_ok());
+        let err = Cell::try_new(&lattice, &positions, &[1], 1e-5).unwrap_err();
+        assert_eq!(
+            err,
+            CellError::LengthMismatch {
+                positions: 2,
+                types: 1
+            }
+        );
+        let err = Cell::try_new(&lattice, &[[0., f64::NAN, 0.]], &[1], 1e-5).unwrap_err();
+        assert_eq!(err, CellError::NonFinite);
+        let flat = [[4., 4., 0.], [0., 0., 0.], [0., 0., 4.]];
+        let err = Cell::try_new(&flat, &positions, &[1, 1], 1e-5).unwrap_err();
+        assert!(matches!(err, CellError::DegenerateLattice { .. }));
+        let left = [[0., 4., 0.], [4., 0., 0.], [0., 0., 4.]];
+        let err = Cell::try_new(&left, &positions, &[1, 1], 1e-5).unwrap_err();
+        assert_eq!(err, CellError::LeftHandedLattice { volume: -64. });
+        // periodic images count, and the tolerance is a Cartesian distance
+        let err =
+            Cell::try_new(&lattice, &[[0., 0., 0.], [0.99999, 0., 1.]], &[1, 1], 1e-3).unwrap_err();
+        assert!(matches!(
+            err,
+            CellError::DuplicateSites {
+                first: 0,
+                second: 1,
+                ..
+            }
+        ));
+        assert!(Cell::try_new(&lattice, &[[0., 0., 0.], [0.99999, 0., 1.]], &[1, 1], 1e-5).is_ok());
+        // like spglib, only atoms of the same type overlap
+        assert!(Cell::try_new(&lattice, &[[0., 0., 0.], [0.99999, 0., 1.]], &[1, 2], 1e-3).is_ok());
+
+        // invalid cells never reach spglib
+        let cell = Cell::new(&lattice, &positions, &[1]);
+        let err = cell.symmetry_operations(1e-5).unwrap_err();
+        assert!(matches!(
+            err.kind,
+            SpglibError::InvalidCell(CellError::LengthMismatch { .. })
+        ));
+        assert_eq!(err.function, "spgat_get_symmetry");
+        assert!(Dataset::new(&cell, 1e-5).is_err());
+        assert!(cell.primitive(1e-5).is_err());
+        let mut cell = Cell::new(&flat, &positions, &[1, 1]);
+        let err = cell.niggli_reduce(1e-5).unwrap_err();
+        assert!(matches!(
+            err.kind,
+            SpglibError::InvalidCell(CellError::DegenerateLattice { .. })
+        ));
+        assert!(err
+            .to_string()
+            .starts_with("spg_niggli_reduce failed: invalid cell"));
     }
 }
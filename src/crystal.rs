@@ -0,0 +1,355 @@
+//! Crystal systems, lattice systems, centerings and Bravais lattices.
+
+use std::fmt;
+
+use crate::pointgroup::PointGroup;
+use crate::spacegroup::SpacegroupNumber;
+
+/// The seven crystal systems.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CrystalSystem {
+    /// Space groups 1-2.
+    Triclinic,
+    /// Space groups 3-15.
+    Monoclinic,
+    /// Space groups 16-74.
+    Orthorhombic,
+    /// Space groups 75-142.
+    Tetragonal,
+    /// Space groups 143-167.
+    Trigonal,
+    /// Space groups 168-194.
+    Hexagonal,
+    /// Space groups 195-230.
+    Cubic,
+}
+
+impl CrystalSystem {
+    /// Returns the crystal system of a space group type.
+    pub fn from_spacegroup_number(number: SpacegroupNumber) -> CrystalSystem {
+        match number.get() {
+            1..=2 => CrystalSystem::Triclinic,
+            3..=15 => CrystalSystem::Monoclinic,
+            16..=74 => CrystalSystem::Orthorhombic,
+            75..=142 => CrystalSystem::Tetragonal,
+            143..=167 => CrystalSystem::Trigonal,
+            168..=194 => CrystalSystem::Hexagonal,
+            _ => CrystalSystem::Cubic,
+        }
+    }
+
+    /// Returns the crystal system of a point group.
+    pub fn from_point_group(pointgroup: PointGroup) -> CrystalSystem {
+        match pointgroup.number() {
+            1..=2 => CrystalSystem::Triclinic,
+            3..=5 => CrystalSystem::Monoclinic,
+            6..=8 => CrystalSystem::Orthorhombic,
+            9..=15 => CrystalSystem::Tetragonal,
+            16..=20 => CrystalSystem::Trigonal,
+            21..=27 => CrystalSystem::Hexagonal,
+            _ => CrystalSystem::Cubic,
+        }
+    }
+
+    /// Returns the name of the crystal system.
+    pub fn name(self) -> &'static str {
+        match self {
+            CrystalSystem::Triclinic => "triclinic",
+            CrystalSystem::Monoclinic => "monoclinic",
+            CrystalSystem::Orthorhombic => "orthorhombic",
+            CrystalSystem::Tetragonal => "tetragonal",
+            CrystalSystem::Trigonal => "trigonal",
+            CrystalSystem::Hexagonal => "hexagonal",
+            CrystalSystem::Cubic => "cubic",
+        }
+    }
+}
+
+impl fmt::Display for CrystalSystem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// The seven lattice systems.
+///
+/// These differ from the crystal systems in splitting the trigonal space groups
+/// by whether their lattice is rhombohedral or hexagonal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LatticeSystem {
+    /// Lattice with holohedry `-1`.
+    Triclinic,
+    /// Lattice with holohedry `2/m`.
+    Monoclinic,
+    /// Lattice with holohedry `mmm`.
+    Orthorhombic,
+    /// Lattice with holohedry `4/mmm`.
+    Tetragonal,
+    /// Lattice with holohedry `-3m`.
+    Rhombohedral,
+    /// Lattice with holohedry `6/mmm`.
+    Hexagonal,
+    /// Lattice with holohedry `m-3m`.
+    Cubic,
+}
+
+impl LatticeSystem {
+    /// Returns the lattice system of a space group type with the given centering.
+    pub fn new(number: SpacegroupNumber, centering: Centering) -> LatticeSystem {
+        match CrystalSystem::from_spacegroup_number(number) {
+            CrystalSystem::Triclinic => LatticeSystem::Triclinic,
+            CrystalSystem::Monoclinic => LatticeSystem::Monoclinic,
+            CrystalSystem::Orthorhombic => LatticeSystem::Orthorhombic,
+            CrystalSystem::Tetragonal => LatticeSystem::Tetragonal,
+            CrystalSystem::Trigonal if centering == Centering::R => LatticeSystem::Rhombohedral,
+            CrystalSystem::Trigonal | CrystalSystem::Hexagonal => LatticeSystem::Hexagonal,
+            CrystalSystem::Cubic => LatticeSystem::Cubic,
+        }
+    }
+
+    /// Returns the point group of the lattice.
+    pub fn holohedry(self) -> PointGroup {
+        match self {
+            LatticeSystem::Triclinic => PointGroup::Ci,
+            LatticeSystem::Monoclinic => PointGroup::C2h,
+            LatticeSystem::Orthorhombic => PointGroup::D2h,
+            LatticeSystem::Tetragonal => PointGroup::D4h,
+            LatticeSystem::Rhombohedral => PointGroup::D3d,
+            LatticeSystem::Hexagonal => PointGroup::D6h,
+            LatticeSystem::Cubic => PointGroup::Oh,
+        }
+    }
+
+    /// Returns the name of the lattice system.
+    pub fn name(self) -> &'static str {
+        match self {
+            LatticeSystem::Triclinic => "triclinic",
+            LatticeSystem::Monoclinic => "monoclinic",
+            LatticeSystem::Orthorhombic => "orthorhombic",
+            LatticeSystem::Tetragonal => "tetragonal",
+            LatticeSystem::Rhombohedral => "rhombohedral",
+            LatticeSystem::Hexagonal => "hexagonal",
+            LatticeSystem::Cubic => "cubic",
+        }
+    }
+}
+
+impl fmt::Display for LatticeSystem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Centering of a conventional cell, named by its Hermann-Mauguin lattice letter.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Centering {
+    /// Primitive.
+    P,
+    /// Centered on the `bc` face.
+    A,
+    /// Centered on the `ca` face.
+    B,
+    /// Centered on the `ab` face.
+    C,
+    /// Body centered.
+    I,
+    /// Face centered.
+    F,
+    /// Rhombohedrally centered, or primitive in rhombohedral axes.
+    R,
+}
+
+impl Centering {
+    /// Returns the centering given by the lattice letter of a Hermann-Mauguin symbol, e.g. `"Fm-3m"`.
+    pub fn from_international(symbol: &str) -> Option<Centering> {
+        match symbol.trim_start().chars().next()? {
+            'P' => Some(Centering::P),
+            'A' => Some(Centering::A),
+            'B' => Some(Centering::B),
+            'C' => Some(Centering::C),
+            'I' => Some(Centering::I),
+            'F' => Some(Centering::F),
+            'R' => Some(Centering::R),
+            _ => None,
+        }
+    }
+
+    /// Returns the Hermann-Mauguin lattice letter.
+    pub fn letter(self) -> char {
+        match self {
+            Centering::P => 'P',
+            Centering::A => 'A',
+            Centering::B => 'B',
+            Centering::C => 'C',
+            Centering::I => 'I',
+            Centering::F => 'F',
+            Centering::R => 'R',
+        }
+    }
+}
+
+impl fmt::Display for Centering {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.letter())
+    }
+}
+
+/// The 14 Bravais lattices, displayed by their Pearson symbol.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BravaisLattice {
+    /// Triclinic primitive, `aP`.
+    TriclinicPrimitive,
+    /// Monoclinic primitive, `mP`.
+    MonoclinicPrimitive,
+    /// Monoclinic base centered, `mS`.
+    MonoclinicBaseCentered,
+    /// Orthorhombic primitive, `oP`.
+    OrthorhombicPrimitive,
+    /// Orthorhombic base centered, `oS`.
+    OrthorhombicBaseCentered,
+    /// Orthorhombic body centered, `oI`.
+    OrthorhombicBodyCentered,
+    /// Orthorhombic face centered, `oF`.
+    OrthorhombicFaceCentered,
+    /// Tetragonal primitive, `tP`.
+    TetragonalPrimitive,
+    /// Tetragonal body centered, `tI`.
+    TetragonalBodyCentered,
+    /// Rhombohedral, `hR`.
+    Rhombohedral,
+    /// Hexagonal primitive, `hP`.
+    HexagonalPrimitive,
+    /// Cubic primitive, `cP`.
+    CubicPrimitive,
+    /// Cubic body centered, `cI`.
+    CubicBodyCentered,
+    /// Cubic face centered, `cF`.
+    CubicFaceCentered,
+}
+
+impl BravaisLattice {
+    /// Returns the Bravais lattice of a space group type with the given centering.
+    ///
+    /// Returns `None` if the centering does not occur in the crystal system of the type.
+    /// Any centering of a monoclinic or orthorhombic base centered lattice is accepted,
+    /// since it depends on the setting.
+    pub fn new(number: SpacegroupNumber, centering: Centering) -> Option<BravaisLattice> {
+        use self::BravaisLattice::*;
+        use self::Centering::*;
+        let lattice = match (LatticeSystem::new(number, centering), centering) {
+            (LatticeSystem::Triclinic, P) => TriclinicPrimitive,
+            (LatticeSystem::Monoclinic, P) => MonoclinicPrimitive,
+            (LatticeSystem::Monoclinic, A | B | C | I) => MonoclinicBaseCentered,
+            (LatticeSystem::Orthorhombic, P) => OrthorhombicPrimitive,
+            (LatticeSystem::Orthorhombic, A | B | C) => OrthorhombicBaseCentered,
+            (LatticeSystem::Orthorhombic, I) => OrthorhombicBodyCentered,
+            (LatticeSystem::Orthorhombic, F) => OrthorhombicFaceCentered,
+            (LatticeSystem::Tetragonal, P) => TetragonalPrimitive,
+            (LatticeSystem::Tetragonal, I) => TetragonalBodyCentered,
+            (LatticeSystem::Rhombohedral, R) => Rhombohedral,
+            (LatticeSystem::Hexagonal, P) => HexagonalPrimitive,
+            (LatticeSystem::Cubic, P) => CubicPrimitive,
+            (LatticeSystem::Cubic, I) => CubicBodyCentered,
+            (LatticeSystem::Cubic, F) => CubicFaceCentered,
+            _ => return None,
+        };
+        Some(lattice)
+    }
+
+    /// Returns the lattice system of the Bravais lattice.
+    pub fn lattice_system(self) -> LatticeSystem {
+        match self {
+            BravaisLattice::TriclinicPrimitive => LatticeSystem::Triclinic,
+            BravaisLattice::MonoclinicPrimitive | BravaisLattice::MonoclinicBaseCentered => {
+                LatticeSystem::Monoclinic
+            }
+            BravaisLattice::OrthorhombicPrimitive
+            | BravaisLattice::OrthorhombicBaseCentered
+            | BravaisLattice::OrthorhombicBodyCentered
+            | BravaisLattice::OrthorhombicFaceCentered => LatticeSystem::Orthorhombic,
+            BravaisLattice::TetragonalPrimitive | BravaisLattice::TetragonalBodyCentered => {
+                LatticeSystem::Tetragonal
+            }
+            BravaisLattice::Rhombohedral => LatticeSystem::Rhombohedral,
+            BravaisLattice::HexagonalPrimitive => LatticeSystem::Hexagonal,
+            BravaisLattice::CubicPrimitive
+            | BravaisLattice::CubicBodyCentered
+            | BravaisLattice::CubicFaceCentered => LatticeSystem::Cubic,
+        }
+    }
+
+    /// Returns the Pearson symbol of the Bravais lattice, e.g. `"cF"`.
+    pub fn pearson_symbol(self) -> &'static str {
+        match self {
+            BravaisLattice::TriclinicPrimitive => "aP",
+            BravaisLattice::MonoclinicPrimitive => "mP",
+            BravaisLattice::MonoclinicBaseCentered => "mS",
+            BravaisLattice::OrthorhombicPrimitive => "oP",
+            BravaisLattice::OrthorhombicBaseCentered => "oS",
+            BravaisLattice::OrthorhombicBodyCentered => "oI",
+            BravaisLattice::OrthorhombicFaceCentered => "oF",
+            BravaisLattice::TetragonalPrimitive => "tP",
+            BravaisLattice::TetragonalBodyCentered => "tI",
+            BravaisLattice::Rhombohedral => "hR",
+            BravaisLattice::HexagonalPrimitive => "hP",
+            BravaisLattice::CubicPrimitive => "cP",
+            BravaisLattice::CubicBodyCentered => "cI",
+            BravaisLattice::CubicFaceCentered => "cF",
+        }
+    }
+}
+
+impl fmt::Display for BravaisLattice {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.pearson_symbol())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::crystal::{BravaisLattice, CrystalSystem, LatticeSystem};
+    use crate::spacegroup::Spacegroup;
+
+    #[test]
+    fn classification_of_all_settings() {
+        let mut types: HashMap<BravaisLattice, Vec<i32>> = HashMap::new();
+        for group in Spacegroup::all_settings() {
+            let bravais = group.bravais_lattice();
+            assert_eq!(bravais.lattice_system(), group.lattice_system());
+            assert_eq!(
+                CrystalSystem::from_point_group(group.point_group()),
+                group.crystal_system()
+            );
+            let numbers = types.entry(bravais).or_default();
+            if !numbers.contains(&group.number) {
+                numbers.push(group.number);
+            }
+        }
+        assert_eq!(types.len(), 14);
+        // numbers of space group types per Bravais lattice
+        let counts: HashMap<&str, usize> = types
+            .iter()
+            .map(|(bravais, numbers)| (bravais.pearson_symbol(), numbers.len()))
+            .collect();
+        assert_eq!(counts["aP"], 2);
+        assert_eq!(counts["mP"], 8);
+        assert_eq!(counts["mS"], 5);
+        assert_eq!(counts["oS"], 15);
+        assert_eq!(counts["hR"], 7);
+        assert_eq!(counts["hP"], 45);
+        assert_eq!(counts["cF"], 11);
+    }
+
+    #[test]
+    fn classification_of_rhombohedral_settings() {
+        // R-3m in hexagonal (hall 458) and rhombohedral (hall 459) axes
+        for hall_number in [458, 459] {
+            let group = Spacegroup::from_hall_number(hall_number).unwrap();
+            assert_eq!(group.crystal_system(), CrystalSystem::Trigonal);
+            assert_eq!(group.lattice_system(), LatticeSystem::Rhombohedral);
+            assert_eq!(group.bravais_lattice().to_string(), "hR");
+        }
+    }
+}
@@ -2,11 +2,17 @@
 
 use std::convert::TryFrom;
 use std::ffi::{CStr, CString};
+use std::slice;
 
 use spglib_sys as ffi;
 
 use crate::cell::Cell;
-use crate::error::SpglibError;
+use crate::crystal::{BravaisLattice, Centering, CrystalSystem, LatticeSystem};
+use crate::error::{Error, SpglibError};
+use crate::pointgroup::PointGroup;
+use crate::spacegroup::{HallNumber, Spacegroup};
+use crate::symmetry::SymmetryOperation;
+use crate::tolerance::Tolerance;
 
 /// Container for a structure's crystallographic properties.
 #[derive(Clone, Debug)]
@@ -37,7 +43,7 @@ pub struct Dataset {
     pub n_atoms: i32,
     /// The wyckoff letters encoded as integer numbers.
     pub wyckoffs: Vec<i32>,
-    /// Site symmetry symbols for a given space group type.
+    /// Site symmetry symbols for a given space group type, indexed like `wyckoffs`.
     pub site_symmetry_symbols: Vec<String>,
     /// The mapping table from the atomic indices of the input cell to the atomic indices of symmetrically independent atoms.
     pub equivalent_atoms: Vec<i32>,
@@ -64,18 +70,37 @@ pub struct Dataset {
 }
 
 // Internal wrapper struct to prevent aliasing of the underlying pointer.
+// The wrapped dataset is owned by spglib and released with `spg_free_dataset` on drop.
 struct SpglibDatasetPointer(*mut ffi::SpglibDataset);
 
+impl Drop for SpglibDatasetPointer {
+    fn drop(&mut self) {
+        if !self.0.is_null() {
+            unsafe { ffi::spg_free_dataset(self.0) };
+        }
+    }
+}
+
+// Copies a C array of `len` elements into Rust-owned storage.
+// A null pointer or non-positive length yields an empty vector.
+unsafe fn copy_array<T: Copy>(ptr: *const T, len: i32) -> Vec<T> {
+    if ptr.is_null() || len <= 0 {
+        return Vec::new();
+    }
+    slice::from_raw_parts(ptr, len as usize).to_vec()
+}
+
 impl TryFrom<SpglibDatasetPointer> for Dataset {
     type Error = SpglibError;
 
     fn try_from(value: SpglibDatasetPointer) -> Result<Self, Self::Error> {
         // dereference the raw pointer
-        let ptr = unsafe { &mut *value.0 };
+        // every array is copied so that nothing outlives `value`, which frees the C dataset when dropped
+        let ptr = unsafe { &*value.0 };
 
         // process fields
-        let spacegroup_number = ptr.spacegroup_number as i32;
-        let hall_number = ptr.hall_number as i32;
+        let spacegroup_number = ptr.spacegroup_number;
+        let hall_number = ptr.hall_number;
         let international_symbol =
             match CString::from(unsafe { CStr::from_ptr(ptr.international_symbol.as_ptr()) })
                 .to_str()
@@ -94,56 +119,31 @@ impl TryFrom<SpglibDatasetPointer> for Dataset {
         };
         let transformation_matrix = ptr.transformation_matrix;
         let origin_shift = ptr.origin_shift;
-        let n_operations = ptr.n_operations as i32;
-        let rotations = unsafe {
-            Vec::from_raw_parts(ptr.rotations, n_operations as usize, n_operations as usize)
-        };
-        let translations = unsafe {
-            Vec::from_raw_parts(
-                ptr.translations,
-                n_operations as usize,
-                n_operations as usize,
-            )
-        };
-        let n_atoms = ptr.n_atoms as i32;
-        let wyckoffs =
-            unsafe { Vec::from_raw_parts(ptr.wyckoffs, n_atoms as usize, n_atoms as usize) };
-        // TODO
-        let site_symmetry_symbols = Vec::new();
-        let equivalent_atoms = unsafe {
-            Vec::from_raw_parts(ptr.equivalent_atoms, n_atoms as usize, n_atoms as usize)
-        };
-        let crystallographic_orbits = unsafe {
-            Vec::from_raw_parts(
-                ptr.crystallographic_orbits,
-                n_atoms as usize,
-                n_atoms as usize,
-            )
-        };
+        let n_operations = ptr.n_operations;
+        let rotations = unsafe { copy_array(ptr.rotations, n_operations) };
+        let translations = unsafe { copy_array(ptr.translations, n_operations) };
+        let n_atoms = ptr.n_atoms;
+        let wyckoffs = unsafe { copy_array(ptr.wyckoffs, n_atoms) };
+        let site_symmetry_symbols = unsafe { copy_array(ptr.site_symmetry_symbols, n_atoms) }
+            .iter()
+            .map(|symbol| {
+                match CString::from(unsafe { CStr::from_ptr(symbol.as_ptr()) }).to_str() {
+                    Ok(s) => Ok(String::from(s)),
+                    Err(_) => Err(SpglibError::Unknown),
+                }
+            })
+            .collect::<Result<Vec<String>, SpglibError>>()?;
+        let equivalent_atoms = unsafe { copy_array(ptr.equivalent_atoms, n_atoms) };
+        let crystallographic_orbits = unsafe { copy_array(ptr.crystallographic_orbits, n_atoms) };
         let primitive_lattice = ptr.primitive_lattice;
-        let mapping_to_primitive = unsafe {
-            Vec::from_raw_parts(ptr.mapping_to_primitive, n_atoms as usize, n_atoms as usize)
-        };
-        let n_std_atoms = ptr.n_std_atoms as i32;
+        let mapping_to_primitive = unsafe { copy_array(ptr.mapping_to_primitive, n_atoms) };
+        let n_std_atoms = ptr.n_std_atoms;
         let std_lattice = ptr.std_lattice;
-        let std_types = unsafe {
-            Vec::from_raw_parts(ptr.std_types, n_std_atoms as usize, n_std_atoms as usize)
-        };
-        let std_positions = unsafe {
-            Vec::from_raw_parts(
-                ptr.std_positions,
-                n_std_atoms as usize,
-                n_std_atoms as usize,
-            )
-        };
+        let std_types = unsafe { copy_array(ptr.std_types, n_std_atoms) };
+        let std_positions = unsafe { copy_array(ptr.std_positions, n_std_atoms) };
         let std_rotation_matrix = ptr.std_rotation_matrix;
-        let std_mapping_to_primitive = unsafe {
-            Vec::from_raw_parts(
-                ptr.std_mapping_to_primitive,
-                n_std_atoms as usize,
-                n_std_atoms as usize,
-            )
-        };
+        let std_mapping_to_primitive =
+            unsafe { copy_array(ptr.std_mapping_to_primitive, n_std_atoms) };
         let pointgroup_symbol =
             match CString::from(unsafe { CStr::from_ptr(ptr.pointgroup_symbol.as_ptr()) }).to_str()
             {
@@ -181,6 +181,9 @@ impl TryFrom<SpglibDatasetPointer> for Dataset {
 
 impl Dataset {
     /// Returns the dataset for a given cell.
+    /// Refer to the full documentation of the implementation [here](https://spglib.github.io/spglib/api.html#spg-get-dataset-and-spg-get-dataset-with-hall-number).
+    ///
+    /// Fails with the spglib error code if no space group could be determined.
     ///
     /// # Example
     ///
@@ -193,20 +196,272 @@ impl Dataset {
     /// let lattice = [[4., 0., 0.], [0., 4., 0.], [0., 0., 4.]];
     /// let positions = [[0., 0., 0.], [0.5, 0.5, 0.5]];
     /// let types = [1, 1];
-    /// let mut bcc_cell = Cell::new(&lattice, &positions, &types);
-    /// let dataset = Dataset::new(&mut bcc_cell, 1e-5);
+    /// let bcc_cell = Cell::new(&lattice, &positions, &types);
+    /// let dataset = Dataset::new(&bcc_cell, 1e-5).unwrap();
     /// assert_eq!(dataset.hall_number, 529);
     /// ```
-    pub fn new(cell: &mut Cell, symprec: f64) -> Dataset {
+    pub fn new(cell: &Cell, tolerance: impl Into<Tolerance>) -> Result<Dataset, Error> {
+        let tolerance = tolerance.into();
+        cell.check("spgat_get_dataset", tolerance)?;
+        Dataset::new_unchecked(cell, tolerance)
+    }
+
+    // Returns the dataset for a cell the caller has already checked with the same tolerance.
+    pub(crate) fn new_unchecked(cell: &Cell, tolerance: Tolerance) -> Result<Dataset, Error> {
         let raw = unsafe {
-            ffi::spg_get_dataset(
+            ffi::spgat_get_dataset(
                 cell.lattice.as_ptr() as *mut [f64; 3],
                 cell.positions.as_ptr() as *mut [f64; 3],
-                cell.types.as_ptr() as *const i32,
+                cell.types.as_ptr(),
                 cell.positions.len() as i32,
-                symprec,
+                tolerance.symprec,
+                tolerance.raw_angle_tolerance(),
+            )
+        };
+        let n_atoms = cell.positions.len();
+        if raw.is_null() {
+            return Err(Error::last("spgat_get_dataset").with_inputs(n_atoms, tolerance));
+        }
+        Dataset::try_from(SpglibDatasetPointer(raw))
+            .map_err(|kind| Error::new(kind, "spgat_get_dataset").with_inputs(n_atoms, tolerance))
+    }
+
+    /// Returns the dataset for a given cell in the setting of the given hall number.
+    /// Refer to the full documentation of the implementation [here](https://spglib.github.io/spglib/api.html#spg-get-dataset-and-spg-get-dataset-with-hall-number).
+    ///
+    /// Fails with [`SpglibError::HallNumberMismatch`] if the hall number does not belong to the
+    /// space group type of the cell.
+    ///
+    /// # Example
+    ///
+    /// Get the dataset for a monoclinic cell with unique axis c (hall number 58) instead of b.
+    ///
+    /// ```
+    /// use spglib::cell::Cell;
+    /// use spglib::dataset::Dataset;
+    /// use spglib::spacegroup::HallNumber;
+    ///
+    /// let beta = 100.0_f64.to_radians();
+    /// let lattice = [
+    ///     [3.0, 0.0, 5.0 * beta.cos()],
+    ///     [0.0, 4.0, 0.0],
+    ///     [0.0, 0.0, 5.0 * beta.sin()],
+    /// ];
+    /// let positions = [[0.0, 0.0, 0.0]];
+    /// let types = [1];
+    /// let cell = Cell::new(&lattice, &positions, &types);
+    /// assert_eq!(Dataset::new(&cell, 1e-5).unwrap().hall_number, 57);
+    /// let hall_number = HallNumber::new(58).unwrap();
+    /// let dataset = Dataset::with_hall_number(&cell, hall_number, 1e-5).unwrap();
+    /// assert_eq!(dataset.hall_number, 58);
+    /// assert_eq!(dataset.choice, "c");
+    /// ```
+    pub fn with_hall_number(
+        cell: &Cell,
+        hall_number: HallNumber,
+        tolerance: impl Into<Tolerance>,
+    ) -> Result<Dataset, Error> {
+        let tolerance = tolerance.into();
+        let n_atoms = cell.positions.len();
+        let function = "spgat_get_dataset_with_hall_number";
+        cell.check(function, tolerance)?;
+        let spacegroup = Spacegroup::from(hall_number);
+        // spglib searches long before giving up on a hall number of another space group type
+        let detected = Dataset::new_unchecked(cell, tolerance)?;
+        if detected.spacegroup_number != spacegroup.number {
+            let kind = SpglibError::HallNumberMismatch {
+                hall_number: hall_number.get(),
+                expected: spacegroup.number,
+                detected: detected.spacegroup_number,
+            };
+            return Err(Error::new(kind, function).with_inputs(n_atoms, tolerance));
+        }
+        let raw = unsafe {
+            ffi::spgat_get_dataset_with_hall_number(
+                cell.lattice.as_ptr() as *mut [f64; 3],
+                cell.positions.as_ptr() as *mut [f64; 3],
+                cell.types.as_ptr(),
+                n_atoms as i32,
+                hall_number.get(),
+                tolerance.symprec,
+                tolerance.raw_angle_tolerance(),
             )
         };
-        Dataset::try_from(SpglibDatasetPointer(raw)).unwrap()
+        if raw.is_null() {
+            return Err(Error::last(function).with_inputs(n_atoms, tolerance));
+        }
+        Dataset::try_from(SpglibDatasetPointer(raw))
+            .map_err(|kind| Error::new(kind, function).with_inputs(n_atoms, tolerance))
+    }
+
+    /// Returns an iterator over the symmetry operations, pairing `rotations` and `translations`.
+    pub fn operations(&self) -> impl Iterator<Item = SymmetryOperation> + '_ {
+        self.rotations
+            .iter()
+            .zip(self.translations.iter())
+            .map(|(rotation, translation)| SymmetryOperation::new(*rotation, *translation))
+    }
+
+    /// Returns the setting the space group was detected in.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spglib::cell::Cell;
+    /// use spglib::dataset::Dataset;
+    ///
+    /// let lattice = [[4., 0., 0.], [0., 4., 0.], [0., 0., 4.]];
+    /// let positions = [[0., 0., 0.], [0.5, 0.5, 0.5]];
+    /// let cell = Cell::new(&lattice, &positions, &[1, 1]);
+    /// let dataset = Dataset::new(&cell, 1e-5).unwrap();
+    /// assert_eq!(dataset.spacegroup().international_short, "Im-3m");
+    /// assert_eq!(dataset.bravais_lattice().to_string(), "cI");
+    /// assert_eq!(format!("{:#}", dataset.point_group()), "Oh");
+    /// ```
+    pub fn spacegroup(&self) -> Spacegroup {
+        let hall_number =
+            HallNumber::new(self.hall_number).expect("spglib detects a tabulated hall number");
+        Spacegroup::from(hall_number)
+    }
+
+    /// Returns the crystal system of the detected space group type.
+    pub fn crystal_system(&self) -> CrystalSystem {
+        self.spacegroup().crystal_system()
+    }
+
+    /// Returns the lattice system of the detected space group type.
+    pub fn lattice_system(&self) -> LatticeSystem {
+        self.spacegroup().lattice_system()
+    }
+
+    /// Returns the centering of the conventional cell in the detected setting.
+    pub fn centering(&self) -> Centering {
+        self.spacegroup().centering()
+    }
+
+    /// Returns the Bravais lattice of the detected space group type.
+    pub fn bravais_lattice(&self) -> BravaisLattice {
+        self.spacegroup().bravais_lattice()
+    }
+
+    /// Returns the point group of the detected space group type.
+    pub fn point_group(&self) -> PointGroup {
+        self.spacegroup().point_group()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cell::Cell;
+    use crate::dataset::Dataset;
+    use crate::error::{CellError, SpglibError};
+    use crate::fixtures::rutile;
+    use crate::spacegroup::HallNumber;
+
+    // These tests exercise the allocation and release of the C dataset. The sanitizer workflow
+    // runs them under AddressSanitizer, which reports allocator mismatches and leaks, e.g.
+    // `RUSTFLAGS="-Zsanitizer=address" cargo +nightly test --lib --target x86_64-unknown-linux-gnu`.
+
+    fn bcc_cell() -> Cell {
+        let lattice = [[4., 0., 0.], [0., 4., 0.], [0., 0., 4.]];
+        let positions = [[0., 0., 0.], [0.5, 0.5, 0.5]];
+        let types = [1, 1];
+        Cell::new(&lattice, &positions, &types)
+    }
+
+    #[test]
+    fn dataset_repeated() {
+        let cell = bcc_cell();
+        let first = Dataset::new(&cell, 1e-5).unwrap();
+        for _ in 0..100 {
+            let dataset = Dataset::new(&cell, 1e-5).unwrap();
+            assert_eq!(dataset.hall_number, first.hall_number);
+            assert_eq!(dataset.rotations, first.rotations);
+            assert_eq!(dataset.translations, first.translations);
+            assert_eq!(dataset.wyckoffs, first.wyckoffs);
+            assert_eq!(dataset.std_positions, first.std_positions);
+        }
+    }
+
+    #[test]
+    fn dataset_owns_arrays() {
+        let cell = bcc_cell();
+        let dataset = Dataset::new(&cell, 1e-5).unwrap();
+        let cloned = dataset.clone();
+        drop(dataset);
+        assert_eq!(cloned.n_operations, 96);
+        assert_eq!(cloned.rotations.len(), 96);
+        assert_eq!(cloned.translations.len(), 96);
+        assert_eq!(cloned.equivalent_atoms, vec![0, 0]);
+        assert_eq!(cloned.std_types.len(), cloned.n_std_atoms as usize);
+    }
+
+    #[test]
+    fn dataset_site_symmetry_symbols() {
+        let dataset = Dataset::new(&rutile(), 1e-5).unwrap();
+        assert_eq!(dataset.spacegroup_number, 136);
+        assert_eq!(
+            dataset.site_symmetry_symbols.len(),
+            dataset.n_atoms as usize
+        );
+        assert_eq!(dataset.wyckoffs, vec![0, 0, 5, 5, 5, 5]);
+        assert_eq!(
+            dataset.site_symmetry_symbols,
+            vec!["m.mm", "m.mm", "m.2m", "m.2m", "m.2m", "m.2m"]
+        );
+    }
+
+    #[test]
+    fn dataset_with_hall_number() {
+        let beta = 100_f64.to_radians();
+        let lattice = [
+            [3., 0., 5. * beta.cos()],
+            [0., 4., 0.],
+            [0., 0., 5. * beta.sin()],
+        ];
+        let cell = Cell::new(&lattice, &[[0., 0., 0.]], &[1]);
+        for hall_number in 57..=59 {
+            let dataset =
+                Dataset::with_hall_number(&cell, HallNumber::new(hall_number).unwrap(), 1e-5)
+                    .unwrap();
+            assert_eq!(dataset.spacegroup_number, 10);
+            assert_eq!(dataset.hall_number, hall_number);
+        }
+        // P2_1/c
+        let err = Dataset::with_hall_number(&cell, HallNumber::new(81).unwrap(), 1e-5).unwrap_err();
+        assert!(matches!(
+            err.kind,
+            SpglibError::HallNumberMismatch {
+                hall_number: 81,
+                expected: 14,
+                detected: 10
+            }
+        ));
+        assert_eq!(
+            err.message,
+            "hall number 81 belongs to space group type 14 but the cell has type 10"
+        );
+    }
+
+    #[test]
+    fn dataset_atoms_too_close() {
+        // the cell is rejected before calling spglib, so the error does not depend on the
+        // error code other tests leave in spglib's global state
+        let lattice = [[4., 0., 0.], [0., 4., 0.], [0., 0., 4.]];
+        let positions = [[0., 0., 0.], [0., 0., 1e-8]];
+        let types = [1, 1];
+        let cell = Cell::new(&lattice, &positions, &types);
+        let err = Dataset::new(&cell, 1e-5).unwrap_err();
+        assert!(matches!(
+            err.kind,
+            SpglibError::InvalidCell(CellError::DuplicateSites {
+                first: 0,
+                second: 1,
+                ..
+            })
+        ));
+        assert_eq!(err.function, "spgat_get_dataset");
+        assert_eq!(err.n_atoms, Some(2));
+        assert_eq!(err.symprec, Some(1e-5));
     }
 }
@@ -0,0 +1,499 @@
+//! Geometric description of symmetry operations.
+
+use std::fmt;
+
+use crate::math::{det_i, mat_mul_i, mat_vec, mat_vec_i};
+use crate::notation::{format_expression, format_number, format_vector};
+use crate::symmetry::SymmetryOperation;
+
+// Smallest magnitude of a pivot when solving for the fixed points of an operation.
+const PIVOT_TOLERANCE: f64 = 1e-8;
+
+/// The kind of a symmetry operation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ElementKind {
+    /// The identity.
+    Identity,
+    /// A pure translation, e.g. a centering translation.
+    Translation,
+    /// An inversion through a point.
+    Inversion,
+    /// An n-fold rotation about an axis.
+    Rotation(u32),
+    /// An n-fold rotation combined with a translation along its axis.
+    Screw(u32),
+    /// A reflection through a plane.
+    Mirror,
+    /// A reflection combined with a translation parallel to its plane.
+    Glide,
+    /// An n-fold rotoinversion, i.e. an n/2-fold or n-fold rotation followed by an inversion
+    /// through a point on its axis. `-2` is a mirror and is classified as such.
+    Rotoinversion(u32),
+}
+
+/// Geometric description of a symmetry operation.
+///
+/// All coordinates are fractional coordinates of the lattice the operation acts on.
+/// Directions are given as the smallest integer vector whose first nonzero component is positive.
+///
+/// # Example
+///
+/// ```
+/// use spglib::element::ElementKind;
+/// use spglib::symmetry::SymmetryOperation;
+///
+/// // 2_1 screw axis along b at x = 0, z = 1/4
+/// let screw = SymmetryOperation::new([[-1, 0, 0], [0, 1, 0], [0, 0, -1]], [0., 0.5, 0.5]);
+/// let element = screw.element();
+/// assert_eq!(element.kind, ElementKind::Screw(2));
+/// assert_eq!(element.axis, Some([0, 1, 0]));
+/// assert_eq!(element.intrinsic_translation, [0., 0.5, 0.]);
+/// assert_eq!(element.ita_symbol(), "2(0,1/2,0) 0,y,1/4");
+/// assert_eq!(element.seitz_symbol(), "{2_010|0 1/2 1/2}");
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct SymmetryElement {
+    /// The operation being described.
+    pub operation: SymmetryOperation,
+    /// The kind of the operation.
+    pub kind: ElementKind,
+    /// The sense of rotation about `axis` for rotations of order 3 or more, counter-clockwise (1)
+    /// or clockwise (-1) when looking down the axis, and 0 otherwise.
+    pub sense: i32,
+    /// The rotation axis, or the plane normal of mirrors and glides.
+    pub axis: Option<[i32; 3]>,
+    /// The screw or glide vector, i.e. the part of the translation along the symmetry element.
+    pub intrinsic_translation: [f64; 3],
+    /// A point on the symmetry element, which is the inversion point for inversions and rotoinversions.
+    /// `None` for the identity and translations.
+    pub location: Option<[f64; 3]>,
+}
+
+impl SymmetryElement {
+    /// Returns the geometric description of an operation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rotation is not crystallographic.
+    pub fn new(operation: &SymmetryOperation) -> SymmetryElement {
+        let rotation = operation.rotation;
+        let det = det_i(&rotation);
+        let trace = rotation[0][0] + rotation[1][1] + rotation[2][2];
+        let order = operation.order();
+
+        // w_g = (1 + W + ... + W^(n-1)) w / n
+        let mut intrinsic_translation = [0.0; 3];
+        let mut power = SymmetryOperation::identity().rotation;
+        for _ in 0..order {
+            let term = mat_vec_i(&power, &operation.translation);
+            for (x, t) in intrinsic_translation.iter_mut().zip(term.iter()) {
+                *x += t / order as f64;
+            }
+            power = mat_mul_i(&rotation, &power);
+        }
+        let intrinsic_translation = intrinsic_translation.map(clean);
+        let is_intrinsic = intrinsic_translation
+            .iter()
+            .any(|x| x.abs() > PIVOT_TOLERANCE);
+
+        let kind = match (det, trace) {
+            (1, 3) if is_intrinsic => ElementKind::Translation,
+            (1, 3) => ElementKind::Identity,
+            (-1, -3) => ElementKind::Inversion,
+            (-1, 1) if is_intrinsic => ElementKind::Glide,
+            (-1, 1) => ElementKind::Mirror,
+            (-1, -1) => ElementKind::Rotoinversion(4),
+            (-1, 0) => ElementKind::Rotoinversion(3),
+            (-1, _) => ElementKind::Rotoinversion(6),
+            _ if is_intrinsic => ElementKind::Screw(order),
+            _ => ElementKind::Rotation(order),
+        };
+
+        let axis = match kind {
+            ElementKind::Identity | ElementKind::Translation | ElementKind::Inversion => None,
+            _ => Some(axis(&rotation, det)),
+        };
+
+        // sense of the proper rotation det * W, as the sign of det(u, v, det * W v)
+        let sense = match (kind, axis) {
+            (ElementKind::Rotation(n), Some(u))
+            | (ElementKind::Screw(n), Some(u))
+            | (ElementKind::Rotoinversion(n), Some(u))
+                if n > 2 =>
+            {
+                let v = [[1, 0, 0], [0, 1, 0], [0, 0, 1]]
+                    .iter()
+                    .copied()
+                    .find(|v| cross(&u, v) != [0, 0, 0])
+                    .expect("some basis vector is not parallel to the axis");
+                let image = mat_vec_i(&rotation, &v.map(|x| x as f64)).map(|x| x * det as f64);
+                let columns = [u.map(|x| x as f64), v.map(|x| x as f64), image];
+                det_columns(&columns).signum() as i32
+            }
+            _ => 0,
+        };
+
+        let location = match kind {
+            ElementKind::Identity | ElementKind::Translation => None,
+            _ => Some(fixed_points(operation, &intrinsic_translation).point()),
+        };
+
+        SymmetryElement {
+            operation: *operation,
+            kind,
+            sense,
+            axis,
+            intrinsic_translation,
+            location,
+        }
+    }
+
+    /// Returns the axis or plane normal as a unit vector in Cartesian coordinates.
+    ///
+    /// The columns of `lattice` are the basis vectors, as in `Cell::lattice`.
+    pub fn cartesian_axis(&self, lattice: &[[f64; 3]; 3]) -> Option<[f64; 3]> {
+        self.axis.map(|u| {
+            let v = mat_vec(lattice, &u.map(|x| x as f64));
+            let norm = (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt();
+            v.map(|x| x / norm)
+        })
+    }
+
+    /// Returns the symbol of the operation as listed in the International Tables for
+    /// Crystallography, e.g. `2(0,1/2,0) 0,y,1/4` or `-4+ 0,0,z; 0,0,0`.
+    pub fn ita_symbol(&self) -> String {
+        let translation = || format!("({})", format_vector(&self.intrinsic_translation));
+        let location = || fixed_points(&self.operation, &self.intrinsic_translation).to_string();
+        let sense = match self.sense {
+            1 => "+",
+            -1 => "-",
+            _ => "",
+        };
+        match self.kind {
+            ElementKind::Identity => "1".to_string(),
+            ElementKind::Translation => format!("t{}", translation()),
+            ElementKind::Inversion => format!("-1 {}", location()),
+            ElementKind::Rotation(n) => format!("{}{} {}", n, sense, location()),
+            ElementKind::Screw(n) => format!("{}{}{} {}", n, sense, translation(), location()),
+            ElementKind::Mirror => format!("m {}", location()),
+            ElementKind::Glide => {
+                let letter = self.glide_letter();
+                if "abc".contains(letter) {
+                    format!("{} {}", letter, location())
+                } else {
+                    format!("{}{} {}", letter, translation(), location())
+                }
+            }
+            ElementKind::Rotoinversion(n) => {
+                // the axis through the inversion point, followed by the inversion point
+                let point = self
+                    .location
+                    .expect("rotoinversions have an inversion point");
+                let proper = SymmetryOperation::new(
+                    self.operation.rotation.map(|row| row.map(|x| -x)),
+                    [0.0; 3],
+                );
+                let origin = proper.apply_point(&point);
+                let shift = [
+                    point[0] - origin[0],
+                    point[1] - origin[1],
+                    point[2] - origin[2],
+                ];
+                let axis = SymmetryOperation::new(proper.rotation, shift);
+                format!(
+                    "-{}{} {}; {}",
+                    n,
+                    sense,
+                    fixed_points(&axis, &[0.0; 3]),
+                    format_vector(&point)
+                )
+            }
+        }
+    }
+
+    /// Returns the Seitz symbol `{R|t}` of the operation, e.g. `{2_010|0 1/2 1/2}`.
+    ///
+    /// The subscript of `R` is the axis, or the plane normal of mirrors and glides.
+    pub fn seitz_symbol(&self) -> String {
+        let sense = match self.sense {
+            1 => "+",
+            -1 => "-",
+            _ => "",
+        };
+        let rotation = match self.kind {
+            ElementKind::Identity | ElementKind::Translation => "1".to_string(),
+            ElementKind::Inversion => "-1".to_string(),
+            ElementKind::Rotation(n) | ElementKind::Screw(n) => format!("{}{}", n, sense),
+            ElementKind::Mirror | ElementKind::Glide => "m".to_string(),
+            ElementKind::Rotoinversion(n) => format!("-{}{}", n, sense),
+        };
+        let rotation = match self.axis {
+            Some(u) => format!(
+                "{}_{}",
+                rotation,
+                u.iter().map(|x| x.to_string()).collect::<String>()
+            ),
+            None => rotation,
+        };
+        let translation = self
+            .operation
+            .translation
+            .iter()
+            .map(|x| format_number(*x))
+            .collect::<Vec<String>>()
+            .join(" ");
+        format!("{{{}|{}}}", rotation, translation)
+    }
+
+    // Returns the letter of a glide: a, b or c along a basis vector, n for halves along a
+    // diagonal, d for quarters and g otherwise.
+    fn glide_letter(&self) -> char {
+        let w = self.intrinsic_translation;
+        let nonzero: Vec<usize> = (0..3).filter(|&i| w[i].abs() > PIVOT_TOLERANCE).collect();
+        let all = |value: f64| nonzero.iter().all(|&i| (w[i].abs() - value).abs() < 1e-6);
+        match nonzero.len() {
+            1 if all(0.5) => ['a', 'b', 'c'][nonzero[0]],
+            _ if all(0.5) => 'n',
+            _ if all(0.25) => 'd',
+            _ => 'g',
+        }
+    }
+}
+
+impl From<&SymmetryOperation> for SymmetryElement {
+    fn from(value: &SymmetryOperation) -> Self {
+        SymmetryElement::new(value)
+    }
+}
+
+/// Formats the element with its ITA symbol.
+impl fmt::Display for SymmetryElement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.ita_symbol())
+    }
+}
+
+// Solution set of a linear system, with coordinate `i` equal to
+// `coefficients[i] . (x, y, z) + constants[i]` for free coordinates x, y and z.
+struct FixedPoints {
+    coefficients: [[f64; 3]; 3],
+    constants: [f64; 3],
+}
+
+impl FixedPoints {
+    // Returns the point with all free coordinates set to zero.
+    fn point(&self) -> [f64; 3] {
+        self.constants.map(clean)
+    }
+}
+
+impl fmt::Display for FixedPoints {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let coordinates: Vec<String> = (0..3)
+            .map(|i| format_expression(&self.coefficients[i], self.constants[i]))
+            .collect();
+        write!(f, "{}", coordinates.join(","))
+    }
+}
+
+// Returns the points on the symmetry element, i.e. the fixed points of the operation
+// with the intrinsic translation removed: W x + w - w_g = x.
+fn fixed_points(operation: &SymmetryOperation, intrinsic_translation: &[f64; 3]) -> FixedPoints {
+    let mut a = [[0.0; 3]; 3];
+    let mut b = [0.0; 3];
+    for (i, (row, bi)) in a.iter_mut().zip(b.iter_mut()).enumerate() {
+        for (j, x) in row.iter_mut().enumerate() {
+            *x = operation.rotation[i][j] as f64 - if i == j { 1.0 } else { 0.0 };
+        }
+        *bi = intrinsic_translation[i] - operation.translation[i];
+    }
+    // reduced row echelon form, pivoting from the last column so that z depends on x and y
+    let mut pivots: Vec<(usize, usize)> = Vec::new();
+    for col in (0..3).rev() {
+        let used: Vec<usize> = pivots.iter().map(|&(row, _)| row).collect();
+        let row = match (0..3)
+            .filter(|r| !used.contains(r))
+            .max_by(|&r, &s| a[r][col].abs().total_cmp(&a[s][col].abs()))
+        {
+            Some(row) if a[row][col].abs() > PIVOT_TOLERANCE => row,
+            _ => continue,
+        };
+        let scale = a[row][col];
+        a[row] = a[row].map(|x| x / scale);
+        b[row] /= scale;
+        for other in 0..3 {
+            if other != row {
+                let factor = a[other][col];
+                let pivot_row = a[row];
+                for (x, p) in a[other].iter_mut().zip(pivot_row.iter()) {
+                    *x -= factor * p;
+                }
+                b[other] -= factor * b[row];
+            }
+        }
+        pivots.push((row, col));
+    }
+    let mut coefficients = [[0.0; 3]; 3];
+    let mut constants = [0.0; 3];
+    let free: Vec<usize> = (0..3)
+        .filter(|col| pivots.iter().all(|&(_, c)| c != *col))
+        .collect();
+    for &col in free.iter() {
+        coefficients[col][col] = 1.0;
+    }
+    for &(row, col) in pivots.iter() {
+        for &j in free.iter() {
+            coefficients[col][j] = -a[row][j];
+        }
+        constants[col] = b[row];
+    }
+    // scale free coordinates to avoid fractional coefficients, e.g. 2x,x,z rather than x,1/2x,z
+    for &j in free.iter() {
+        let multiple = (1..=6)
+            .find(|m| {
+                (0..3).all(|i| {
+                    let c = coefficients[i][j] * *m as f64;
+                    (c - c.round()).abs() < PIVOT_TOLERANCE
+                })
+            })
+            .unwrap_or(1);
+        for row in coefficients.iter_mut() {
+            row[j] *= multiple as f64;
+        }
+    }
+    FixedPoints {
+        coefficients: coefficients.map(|row| row.map(clean)),
+        constants: constants.map(clean),
+    }
+}
+
+// Returns the axis of a rotation, or the normal of a mirror, as the kernel of W - det * 1.
+fn axis(rotation: &[[i32; 3]; 3], det: i32) -> [i32; 3] {
+    let mut m = *rotation;
+    for (i, row) in m.iter_mut().enumerate() {
+        row[i] -= det;
+    }
+    // the kernel of a rank 2 matrix is spanned by the cross product of two independent rows
+    let mut u = [(0, 1), (0, 2), (1, 2)]
+        .iter()
+        .map(|&(i, j)| cross(&m[i], &m[j]))
+        .find(|u| *u != [0, 0, 0])
+        .expect("the rotation is not the identity or inversion");
+    let divisor = u.iter().fold(0, |acc, &x| gcd(acc, x.abs()));
+    let sign = if u.iter().find(|&&x| x != 0).copied().unwrap_or(1) < 0 {
+        -1
+    } else {
+        1
+    };
+    for x in u.iter_mut() {
+        *x = *x / divisor * sign;
+    }
+    u
+}
+
+fn cross(a: &[i32; 3], b: &[i32; 3]) -> [i32; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn gcd(a: i32, b: i32) -> i32 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+// Returns the determinant of the matrix with the given columns.
+fn det_columns(c: &[[f64; 3]; 3]) -> f64 {
+    c[0][0] * (c[1][1] * c[2][2] - c[1][2] * c[2][1])
+        - c[1][0] * (c[0][1] * c[2][2] - c[0][2] * c[2][1])
+        + c[2][0] * (c[0][1] * c[1][2] - c[0][2] * c[1][1])
+}
+
+// Rounds values within the tolerance of zero to zero, avoiding `-0` in symbols.
+fn clean(x: f64) -> f64 {
+    if x.abs() < PIVOT_TOLERANCE {
+        0.0
+    } else {
+        x
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cell::Cell;
+    use crate::dataset::Dataset;
+    use crate::element::{ElementKind, SymmetryElement};
+    use crate::fixtures::rutile;
+
+    #[test]
+    fn ita_symbols_of_p42_mnm() {
+        let dataset = Dataset::new(&rutile(), 1e-5).unwrap();
+        let mut symbols: Vec<String> = dataset
+            .operations()
+            .map(|operation| operation.element().ita_symbol())
+            .collect();
+        symbols.sort();
+        // general position of space group 136 in the International Tables
+        let mut expected = vec![
+            "1",
+            "2 0,0,z",
+            "4+(0,0,1/2) 0,1/2,z",
+            "4-(0,0,1/2) 1/2,0,z",
+            "2(1/2,0,0) x,1/4,1/4",
+            "2(0,1/2,0) 1/4,y,1/4",
+            "2 x,x,0",
+            "2 x,-x,0",
+            "-1 0,0,0",
+            "m x,y,0",
+            "-4- 0,1/2,z; 0,1/2,1/4",
+            "-4+ 1/2,0,z; 1/2,0,1/4",
+            "n(0,1/2,1/2) 1/4,y,z",
+            "n(1/2,0,1/2) x,1/4,z",
+            "m x,-x,z",
+            "m x,x,z",
+        ];
+        expected.sort_unstable();
+        assert_eq!(symbols, expected);
+    }
+
+    #[test]
+    fn elements_of_hexagonal_operations() {
+        let lattice = [[3., -1.5, 0.], [0., 1.5 * 3f64.sqrt(), 0.], [0., 0., 5.]];
+        let positions = [[1. / 3., 2. / 3., 0.25], [2. / 3., 1. / 3., 0.75]];
+        // hexagonal close packing, P6_3/mmc
+        let cell = Cell::new(&lattice, &positions, &[1, 1]);
+        let dataset = Dataset::new(&cell, 1e-5).unwrap();
+        assert_eq!(dataset.spacegroup_number, 194);
+        let elements: Vec<SymmetryElement> = dataset.operations().map(|op| op.element()).collect();
+        let count = |kind: ElementKind| elements.iter().filter(|e| e.kind == kind).count();
+        assert_eq!(count(ElementKind::Identity), 1);
+        assert_eq!(count(ElementKind::Inversion), 1);
+        assert_eq!(count(ElementKind::Screw(6)), 2);
+        assert_eq!(count(ElementKind::Rotation(3)), 2);
+        assert_eq!(count(ElementKind::Rotoinversion(6)), 2);
+        assert_eq!(count(ElementKind::Rotoinversion(3)), 2);
+        for element in elements.iter() {
+            if let Some(axis) = element.cartesian_axis(&lattice) {
+                let norm = axis.iter().map(|x| x * x).sum::<f64>();
+                assert!((norm - 1.0).abs() < 1e-12);
+                if let ElementKind::Screw(6) = element.kind {
+                    assert_eq!(axis, [0., 0., 1.]);
+                    assert!((element.intrinsic_translation[2] - 0.5).abs() < 1e-12);
+                }
+            }
+            // every point on the element is mapped to itself up to the intrinsic translation
+            if let Some(point) = element.location {
+                let image = element.operation.apply_point(&point);
+                for i in 0..3 {
+                    let d = image[i] - point[i] - element.intrinsic_translation[i];
+                    assert!((d - d.round()).abs() < 1e-8);
+                }
+            }
+        }
+    }
+}
@@ -2,10 +2,13 @@
 
 use std::convert::From;
 use std::error;
+use std::ffi::CStr;
 use std::fmt;
 
 use spglib_sys as ffi;
 
+use crate::tolerance::Tolerance;
+
 /// Possible error codes.
 #[derive(Clone, Debug)]
 pub enum SpglibError {
@@ -25,6 +28,32 @@ pub enum SpglibError {
     DelaunayFailed,
     /// Raised when an array argument has insufficient capacity.
     ArraySizeShortage,
+    /// Raised when the atoms of a cell returned by spglib cannot be matched to the input atoms.
+    AtomMappingFailed,
+    /// Raised before calling spglib when the cell fails validation.
+    InvalidCell(CellError),
+    /// Raised before calling spglib when symmetry operations, q-points or a lattice other than
+    /// that of a cell fail validation.
+    InvalidInput(InputError),
+    /// Raised before calling spglib when a mesh dimension is not positive or the mesh has more
+    /// than 2^24 points.
+    InvalidMesh([i32; 3]),
+    /// Raised when a hall number belongs to another space group type than that of the cell.
+    HallNumberMismatch {
+        /// The requested hall number.
+        hall_number: i32,
+        /// Space group type number the hall number belongs to.
+        expected: i32,
+        /// Space group type number detected for the cell.
+        detected: i32,
+    },
+    /// Raised when a space group or hall number lies outside of the range `1..=max`.
+    NumberOutOfRange {
+        /// The rejected number.
+        value: i32,
+        /// The largest valid number.
+        max: i32,
+    },
     /// Raised for any unknown errors.
     Unknown,
 }
@@ -58,9 +87,300 @@ impl fmt::Display for SpglibError {
             SpglibError::NiggliFailed => write!(f, "niggli failed"),
             SpglibError::DelaunayFailed => write!(f, "delaunay failed"),
             SpglibError::ArraySizeShortage => write!(f, "array size shortage"),
+            SpglibError::AtomMappingFailed => write!(f, "atom mapping failed"),
+            SpglibError::InvalidCell(ref err) => write!(f, "invalid cell: {}", err),
+            SpglibError::InvalidInput(ref err) => write!(f, "invalid input: {}", err),
+            SpglibError::InvalidMesh(mesh) => {
+                write!(f, "invalid mesh {}x{}x{}", mesh[0], mesh[1], mesh[2])
+            }
+            SpglibError::HallNumberMismatch {
+                hall_number,
+                expected,
+                detected,
+            } => write!(
+                f,
+                "hall number {} belongs to space group type {} but the cell has type {}",
+                hall_number, expected, detected
+            ),
+            SpglibError::NumberOutOfRange { value, max } => {
+                write!(f, "number {} is outside of the range 1-{}", value, max)
+            }
             SpglibError::Unknown => write!(f, "unknown error"),
         }
     }
 }
 
 impl error::Error for SpglibError {}
+
+/// Error raised by a failed call into spglib.
+///
+/// Carries the spglib error code along with the inputs that mattered to the failing call.
+#[derive(Clone, Debug)]
+pub struct Error {
+    /// The spglib error code.
+    pub kind: SpglibError,
+    /// The message spglib associates with the error code.
+    pub message: String,
+    /// Name of the spglib function which failed, empty if the error was raised without calling
+    /// into spglib.
+    pub function: &'static str,
+    /// Number of atoms passed to the failing function.
+    pub n_atoms: Option<usize>,
+    /// Symmetry search tolerance passed to the failing function.
+    pub symprec: Option<f64>,
+    /// Angle tolerance passed to the failing function.
+    pub angle_tolerance: Option<f64>,
+}
+
+impl Error {
+    // Returns a new error with the message of the given kind.
+    pub(crate) fn new(kind: SpglibError, function: &'static str) -> Error {
+        let message = kind.to_string();
+        Error {
+            kind,
+            message,
+            function,
+            n_atoms: None,
+            symprec: None,
+            angle_tolerance: None,
+        }
+    }
+
+    // Returns a new error from the code of the last spglib call.
+    pub(crate) fn last(function: &'static str) -> Error {
+        let code = unsafe { ffi::spg_get_error_code() };
+        let kind = SpglibError::from(code);
+        // spglib does not set an error code on every failure path
+        if let SpglibError::Unknown = kind {
+            return Error::new(kind, function);
+        }
+        let raw = unsafe { ffi::spg_get_error_message(code) };
+        let message = if raw.is_null() {
+            kind.to_string()
+        } else {
+            unsafe { CStr::from_ptr(raw) }
+                .to_string_lossy()
+                .into_owned()
+        };
+        Error {
+            kind,
+            message,
+            function,
+            n_atoms: None,
+            symprec: None,
+            angle_tolerance: None,
+        }
+    }
+
+    // Replaces the message of the error.
+    pub(crate) fn with_message(mut self, message: String) -> Error {
+        self.message = message;
+        self
+    }
+
+    // Attaches the cell size and tolerances of the failing call.
+    pub(crate) fn with_inputs(mut self, n_atoms: usize, tolerance: Tolerance) -> Error {
+        self.n_atoms = Some(n_atoms);
+        self.symprec = Some(tolerance.symprec);
+        self.angle_tolerance = tolerance.angle_tolerance;
+        self
+    }
+
+    // Returns a new error for a cell rejected before calling the given function.
+    pub(crate) fn invalid_cell(err: CellError, function: &'static str) -> Error {
+        Error::new(SpglibError::InvalidCell(err), function)
+    }
+
+    // Returns a new error for a space group or hall number outside of the range 1-max.
+    pub(crate) fn out_of_range(value: i32, max: i32) -> Error {
+        Error::new(SpglibError::NumberOutOfRange { value, max }, "")
+    }
+
+    // Returns a new error for an input rejected before calling the given function.
+    pub(crate) fn invalid_input(err: InputError, function: &'static str) -> Error {
+        Error::new(SpglibError::InvalidInput(err), function)
+    }
+
+    // Attaches the tolerance of the failing call.
+    pub(crate) fn with_symprec(mut self, symprec: f64) -> Error {
+        self.symprec = Some(symprec);
+        self
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.function.is_empty() {
+            write!(f, "{}", self.message)?;
+        } else {
+            write!(f, "{} failed: {}", self.function, self.message)?;
+        }
+        let mut inputs = Vec::new();
+        if let Some(n_atoms) = self.n_atoms {
+            inputs.push(format!("n_atoms: {}", n_atoms));
+        }
+        if let Some(symprec) = self.symprec {
+            inputs.push(format!("symprec: {}", symprec));
+        }
+        if let Some(angle_tolerance) = self.angle_tolerance {
+            inputs.push(format!("angle_tolerance: {}", angle_tolerance));
+        }
+        if !inputs.is_empty() {
+            write!(f, " ({})", inputs.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+impl error::Error for Error {}
+
+/// Reason a cell is rejected before it is passed to spglib.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CellError {
+    /// The numbers of positions and types differ.
+    LengthMismatch {
+        /// Number of positions.
+        positions: usize,
+        /// Number of types.
+        types: usize,
+    },
+    /// The numbers of atoms and spins differ.
+    SpinCountMismatch {
+        /// Number of atoms.
+        atoms: usize,
+        /// Number of spins.
+        spins: usize,
+    },
+    /// The lattice, a position or a spin contains a NaN or infinite value.
+    NonFinite,
+    /// The lattice vectors are linearly dependent or nearly so.
+    DegenerateLattice {
+        /// Volume of the cell.
+        volume: f64,
+    },
+    /// The lattice vectors form a left-handed basis.
+    LeftHandedLattice {
+        /// Volume of the cell, which is negative.
+        volume: f64,
+    },
+    /// Two atoms of the same type are closer than the tolerance, taking periodic images into
+    /// account.
+    DuplicateSites {
+        /// Index of the first atom.
+        first: usize,
+        /// Index of the second atom.
+        second: usize,
+        /// Distance between the two atoms.
+        distance: f64,
+    },
+}
+
+impl fmt::Display for CellError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            CellError::LengthMismatch { positions, types } => {
+                write!(f, "{} positions but {} types were given", positions, types)
+            }
+            CellError::SpinCountMismatch { atoms, spins } => {
+                write!(f, "{} atoms but {} spins were given", atoms, spins)
+            }
+            CellError::NonFinite => write!(f, "lattice, positions or spins are not finite"),
+            CellError::DegenerateLattice { volume } => {
+                write!(f, "lattice is degenerate with volume {}", volume)
+            }
+            CellError::LeftHandedLattice { volume } => {
+                write!(f, "lattice is left-handed with volume {}", volume)
+            }
+            CellError::DuplicateSites {
+                first,
+                second,
+                distance,
+            } => write!(
+                f,
+                "atoms {} and {} are only {} apart",
+                first, second, distance
+            ),
+        }
+    }
+}
+
+impl error::Error for CellError {}
+
+/// Reason symmetry operations, q-points or a lattice are rejected before they are passed to
+/// spglib.
+#[derive(Clone, Debug, PartialEq)]
+pub enum InputError {
+    /// The numbers of rotations and translations differ.
+    LengthMismatch {
+        /// Number of rotations.
+        rotations: usize,
+        /// Number of translations.
+        translations: usize,
+    },
+    /// No rotations were given.
+    NoRotations,
+    /// A rotation does not have determinant 1 or -1.
+    NotUnimodular {
+        /// Index of the rotation.
+        index: usize,
+    },
+    /// A translation, q-point or lattice contains a NaN or infinite value.
+    NonFinite,
+    /// The lattice vectors are linearly dependent or nearly so.
+    DegenerateLattice {
+        /// Volume of the lattice.
+        volume: f64,
+    },
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            InputError::LengthMismatch {
+                rotations,
+                translations,
+            } => write!(
+                f,
+                "{} rotations but {} translations were given",
+                rotations, translations
+            ),
+            InputError::NoRotations => write!(f, "no rotations were given"),
+            InputError::NotUnimodular { index } => {
+                write!(f, "rotation {} does not have determinant 1 or -1", index)
+            }
+            InputError::NonFinite => write!(f, "input is not finite"),
+            InputError::DegenerateLattice { volume } => {
+                write!(f, "lattice is degenerate with volume {}", volume)
+            }
+        }
+    }
+}
+
+impl error::Error for InputError {}
+
+/// Error raised when a symbol or notation cannot be parsed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseError {
+    /// The text that failed to parse.
+    pub input: String,
+    /// Description of what is wrong with the input.
+    pub message: String,
+}
+
+impl ParseError {
+    // Returns a new error for the given input.
+    pub(crate) fn new(input: &str, message: impl Into<String>) -> ParseError {
+        ParseError {
+            input: input.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "cannot parse {:?}: {}", self.input, self.message)
+    }
+}
+
+impl error::Error for ParseError {}
@@ -0,0 +1,18 @@
+//! Structures shared by the unit tests.
+
+use crate::cell::Cell;
+
+// Rutile TiO2 in P4_2/mnm, with titanium on 2a and oxygen on 4f.
+pub(crate) fn rutile() -> Cell {
+    let x = 0.3048;
+    let lattice = [[4.59, 0., 0.], [0., 4.59, 0.], [0., 0., 2.96]];
+    let positions = [
+        [0., 0., 0.],
+        [0.5, 0.5, 0.5],
+        [x, x, 0.],
+        [-x, -x, 0.],
+        [0.5 + x, 0.5 - x, 0.5],
+        [0.5 - x, 0.5 + x, 0.5],
+    ];
+    Cell::new(&lattice, &positions, &[22, 22, 8, 8, 8, 8])
+}
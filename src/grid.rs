@@ -0,0 +1,404 @@
+//! Grid-point utilities for uniform reciprocal-space meshes.
+
+use std::os::raw::c_int;
+
+use spglib_sys as ffi;
+
+use crate::cell::validate_lattice;
+use crate::error::{CellError, Error, InputError, SpglibError};
+use crate::mesh::mesh_size;
+
+/// Uniform reciprocal-space mesh with an optional half-grid shift.
+///
+/// Grid points are indexed with the first axis running fastest, and the address of a grid point
+/// is reduced to `-mesh / 2 < address <= mesh / 2`. A grid point with address `a` lies at the
+/// reduced coordinates `(2 * a + s) / (2 * mesh)`, where `s` is 1 along the shifted axes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GridMesh {
+    mesh: [i32; 3],
+    is_shift: [bool; 3],
+}
+
+impl GridMesh {
+    /// Returns a mesh with the given number of grid points and half-grid shift along each axis.
+    ///
+    /// Fails with [`SpglibError::InvalidMesh`] if any mesh dimension is not positive, or if the
+    /// doubled mesh used for the Brillouin zone has more than 2^24 points.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spglib::grid::GridMesh;
+    ///
+    /// assert_eq!(GridMesh::new((3, 5, 2), (false, false, false)).unwrap().n_grid_points(), 30);
+    /// assert!(GridMesh::new((4, 0, 4), (false, false, false)).is_err());
+    /// assert!(GridMesh::new((2048, 2048, 2048), (false, false, false)).is_err());
+    /// ```
+    pub fn new(mesh: (i32, i32, i32), shift: (bool, bool, bool)) -> Result<GridMesh, Error> {
+        let mesh = [mesh.0, mesh.1, mesh.2];
+        let doubled = mesh.map(|m| m.saturating_mul(2));
+        if mesh_size(&doubled).is_none() {
+            return Err(Error::new(
+                SpglibError::InvalidMesh(mesh),
+                "spg_relocate_BZ_grid_address",
+            ));
+        }
+        Ok(GridMesh {
+            mesh,
+            is_shift: [shift.0, shift.1, shift.2],
+        })
+    }
+
+    /// Returns the number of grid points along each axis.
+    pub fn mesh(&self) -> [i32; 3] {
+        self.mesh
+    }
+
+    /// Returns the half-grid shift along each axis.
+    pub fn is_shift(&self) -> [bool; 3] {
+        self.is_shift
+    }
+
+    /// Returns the total number of grid points.
+    pub fn n_grid_points(&self) -> usize {
+        self.mesh.iter().map(|&m| m as usize).product()
+    }
+
+    /// Returns the index of the grid point at an address.
+    /// Refer to the full documentation of the implementation [here](https://spglib.github.io/spglib/api.html#spg-get-grid-point-from-address).
+    ///
+    /// Addresses outside the reduced range are wrapped back into the mesh.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spglib::grid::GridMesh;
+    ///
+    /// let grid = GridMesh::new((4, 4, 4), (false, false, false)).unwrap();
+    /// assert_eq!(grid.grid_point_from_address([-1, 1, 0]), 7);
+    /// assert_eq!(grid.grid_point_from_address([3, 5, 4]), 7);
+    /// assert_eq!(grid.address_from_grid_point(7), Some([-1, 1, 0]));
+    /// ```
+    pub fn grid_point_from_address(&self, address: [i32; 3]) -> usize {
+        unsafe {
+            ffi::spg_get_grid_point_from_address(address.as_ptr(), self.mesh.as_ptr()) as usize
+        }
+    }
+
+    /// Returns the reduced address of a grid point, or `None` if the index is not smaller than
+    /// the number of grid points.
+    pub fn address_from_grid_point(&self, grid_point: usize) -> Option<[i32; 3]> {
+        if grid_point < self.n_grid_points() {
+            Some(self.address(grid_point))
+        } else {
+            None
+        }
+    }
+
+    // Returns the reduced address of a grid point inside the mesh.
+    fn address(&self, grid_point: usize) -> [i32; 3] {
+        let mut address = [0; 3];
+        let mut rest = grid_point;
+        for (a, &m) in address.iter_mut().zip(self.mesh.iter()) {
+            *a = (rest % m as usize) as i32;
+            rest /= m as usize;
+            if *a > m / 2 {
+                *a -= m;
+            }
+        }
+        address
+    }
+
+    /// Returns the reduced addresses of all grid points, in order of their index.
+    pub fn grid_addresses(&self) -> Vec<[i32; 3]> {
+        (0..self.n_grid_points()).map(|i| self.address(i)).collect()
+    }
+
+    /// Returns the reduced coordinates of the point at an address.
+    pub fn qpoint(&self, address: [i32; 3]) -> [f64; 3] {
+        let mut qpoint = [0.0; 3];
+        for i in 0..3 {
+            let shift = if self.is_shift[i] { 1 } else { 0 };
+            qpoint[i] = (2 * address[i] + shift) as f64 / (2 * self.mesh[i]) as f64;
+        }
+        qpoint
+    }
+
+    /// Returns the indices of the grid points that an address is sent to by each rotation.
+    /// Refer to the full documentation of the implementation [here](https://spglib.github.io/spglib/api.html#spg-get-grid-points-by-rotations).
+    ///
+    /// The rotations act on reciprocal space, i.e. they are the transposes of `Dataset::rotations`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spglib::grid::GridMesh;
+    ///
+    /// let grid = GridMesh::new((4, 4, 4), (false, false, false)).unwrap();
+    /// let inversion = [[-1, 0, 0], [0, -1, 0], [0, 0, -1]];
+    /// let points = grid.grid_points_by_rotations([1, 0, 0], &[inversion]);
+    /// assert_eq!(grid.address_from_grid_point(points[0]), Some([-1, 0, 0]));
+    /// ```
+    pub fn grid_points_by_rotations(
+        &self,
+        address: [i32; 3],
+        rotations: &[[[i32; 3]; 3]],
+    ) -> Vec<usize> {
+        // the non-dense variant is declared but not defined in the C library
+        let is_shift = self.raw_shift();
+        let mut grid_points: Vec<ffi::size_t> = vec![0; rotations.len()];
+        unsafe {
+            ffi::spg_get_dense_grid_points_by_rotations(
+                grid_points.as_mut_ptr(),
+                address.as_ptr(),
+                rotations.len() as c_int,
+                rotations.as_ptr() as *mut [[c_int; 3]; 3],
+                self.mesh.as_ptr(),
+                is_shift.as_ptr(),
+            )
+        };
+        grid_points.into_iter().map(|i| i as usize).collect()
+    }
+
+    /// Moves the grid points into the first Brillouin zone.
+    /// Refer to the full documentation of the implementation [here](https://spglib.github.io/spglib/api.html#spg-relocate-bz-grid-address).
+    ///
+    /// The columns of `reciprocal_lattice` are the reciprocal basis vectors.
+    /// Grid points on the zone boundary are repeated for every equally short translation.
+    /// Fails with [`SpglibError::InvalidInput`] if the reciprocal lattice is not finite or its
+    /// vectors are (nearly) linearly dependent.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spglib::grid::GridMesh;
+    ///
+    /// let grid = GridMesh::new((4, 4, 4), (false, false, false)).unwrap();
+    /// let reciprocal_lattice = [[0.25, 0., 0.], [0., 0.25, 0.], [0., 0., 0.25]];
+    /// let zone = grid.relocate_to_brillouin_zone(&reciprocal_lattice).unwrap();
+    /// // (2, 2, 2) is a corner of the cube
+    /// let corner = grid.grid_point_from_address([2, 2, 2]);
+    /// assert_eq!(zone.multiplicities[corner], 8);
+    /// assert_eq!(zone.grid_address.len(), 125);
+    /// ```
+    pub fn relocate_to_brillouin_zone(
+        &self,
+        reciprocal_lattice: &[[f64; 3]; 3],
+    ) -> Result<BrillouinZoneGrid, Error> {
+        let function = "spg_relocate_BZ_grid_address";
+        // the handedness of the reciprocal basis does not matter to the search
+        match validate_lattice(reciprocal_lattice) {
+            Ok(()) | Err(CellError::LeftHandedLattice { .. }) => {}
+            Err(CellError::DegenerateLattice { volume }) => {
+                let err = InputError::DegenerateLattice { volume };
+                return Err(Error::invalid_input(err, function));
+            }
+            Err(_) => return Err(Error::invalid_input(InputError::NonFinite, function)),
+        }
+        let n_grid_points = self.n_grid_points();
+        let is_shift = self.raw_shift();
+        let mut grid_address = self.grid_addresses();
+        let n_bz = self.mesh.iter().map(|&m| m as usize + 1).product();
+        let n_map = self.mesh.iter().map(|&m| 2 * m as usize).product();
+        let mut bz_grid_address = vec![[0; 3]; n_bz];
+        let mut bz_map = vec![0; n_map];
+        let res = unsafe {
+            ffi::spg_relocate_BZ_grid_address(
+                bz_grid_address.as_mut_ptr(),
+                bz_map.as_mut_ptr(),
+                grid_address.as_mut_ptr(),
+                self.mesh.as_ptr(),
+                reciprocal_lattice.as_ptr() as *mut [f64; 3],
+                is_shift.as_ptr(),
+            )
+        };
+        if res == 0 {
+            return Err(Error::last(function));
+        }
+        bz_grid_address.truncate(res as usize);
+        let mut multiplicities = vec![0; n_grid_points];
+        for address in bz_grid_address.iter() {
+            multiplicities[self.grid_point_from_address(*address)] += 1;
+        }
+        Ok(BrillouinZoneGrid {
+            grid: *self,
+            grid_address: bz_grid_address,
+            map: bz_map
+                .into_iter()
+                .map(|i| if i < 0 { None } else { Some(i as usize) })
+                .collect(),
+            multiplicities,
+        })
+    }
+
+    fn raw_shift(&self) -> [c_int; 3] {
+        self.is_shift.map(|s| s as c_int)
+    }
+}
+
+/// Grid points of a mesh relocated into the first Brillouin zone.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BrillouinZoneGrid {
+    /// Mesh the points belong to.
+    pub grid: GridMesh,
+    /// Addresses of the relocated grid points.
+    /// The first entries follow the grid point order of the mesh, and the additional
+    /// zone-boundary images are appended after them.
+    pub grid_address: Vec<[i32; 3]>,
+    /// Index into `grid_address` for each point of the doubled mesh, if it lies in the zone.
+    pub map: Vec<Option<usize>>,
+    /// Number of images in the zone of each grid point of the mesh.
+    pub multiplicities: Vec<usize>,
+}
+
+impl BrillouinZoneGrid {
+    /// Returns the indices into `grid_address` that an address is sent to by each rotation,
+    /// or `None` where the image is not a point of the zone.
+    /// Refer to the full documentation of the implementation [here](https://spglib.github.io/spglib/api.html#spg-get-bz-grid-points-by-rotations).
+    ///
+    /// The rotations act on reciprocal space, i.e. they are the transposes of `Dataset::rotations`.
+    pub fn grid_points_by_rotations(
+        &self,
+        address: [i32; 3],
+        rotations: &[[[i32; 3]; 3]],
+    ) -> Vec<Option<usize>> {
+        let n_map = self.map.len() as ffi::size_t;
+        let bz_map: Vec<ffi::size_t> = self
+            .map
+            .iter()
+            .map(|i| i.map_or(n_map, |i| i as ffi::size_t))
+            .collect();
+        let mesh = self.grid.mesh;
+        let is_shift = self.grid.raw_shift();
+        let mut grid_points: Vec<ffi::size_t> = vec![0; rotations.len()];
+        unsafe {
+            ffi::spg_get_dense_BZ_grid_points_by_rotations(
+                grid_points.as_mut_ptr(),
+                address.as_ptr(),
+                rotations.len() as c_int,
+                rotations.as_ptr() as *mut [[c_int; 3]; 3],
+                mesh.as_ptr(),
+                is_shift.as_ptr(),
+                bz_map.as_ptr(),
+            )
+        };
+        grid_points
+            .into_iter()
+            .map(|i| if i == n_map { None } else { Some(i as usize) })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cell::Cell;
+    use crate::dataset::Dataset;
+    use crate::error::{InputError, SpglibError};
+    use crate::grid::GridMesh;
+
+    fn transpose(m: &[[i32; 3]; 3]) -> [[i32; 3]; 3] {
+        let mut t = [[0; 3]; 3];
+        for i in 0..3 {
+            for j in 0..3 {
+                t[i][j] = m[j][i];
+            }
+        }
+        t
+    }
+
+    #[test]
+    fn grid_addresses_match_ir_mesh() {
+        let lattice = [[4., 0., 0.], [0., 4., 0.], [0., 0., 4.]];
+        let cell = Cell::new(&lattice, &[[0., 0., 0.]], &[1]);
+        for &(mesh, shift) in [
+            ((4, 4, 4), (false, false, false)),
+            ((3, 5, 2), (true, false, true)),
+        ]
+        .iter()
+        {
+            let grid = GridMesh::new(mesh, shift).unwrap();
+            let ir_mesh = cell.ir_reciprocal_mesh(mesh, shift, true, 1e-5).unwrap();
+            assert_eq!(grid.grid_addresses(), ir_mesh.grid_address);
+            for (i, address) in ir_mesh.grid_address.iter().enumerate() {
+                assert_eq!(grid.grid_point_from_address(*address), i);
+            }
+        }
+    }
+
+    #[test]
+    fn grid_points_by_rotations_form_stars() {
+        let lattice = [[4., 0., 0.], [0., 4., 0.], [0., 0., 4.]];
+        let cell = Cell::new(&lattice, &[[0., 0., 0.]], &[1]);
+        let dataset = Dataset::new(&cell, 1e-5).unwrap();
+        let rotations: Vec<_> = dataset.rotations.iter().map(transpose).collect();
+        let grid = GridMesh::new((4, 4, 4), (false, false, false)).unwrap();
+        let ir_mesh = cell
+            .ir_reciprocal_mesh((4, 4, 4), (false, false, false), false, 1e-5)
+            .unwrap();
+        for (i, address) in ir_mesh.grid_address.iter().enumerate() {
+            let mut star = grid.grid_points_by_rotations(*address, &rotations);
+            star.sort_unstable();
+            star.dedup();
+            let expected: Vec<usize> = (0..ir_mesh.mapping.len())
+                .filter(|&j| ir_mesh.mapping[j] == ir_mesh.mapping[i])
+                .collect();
+            assert_eq!(star, expected);
+        }
+    }
+
+    #[test]
+    fn brillouin_zone_boundary() {
+        let grid = GridMesh::new((4, 4, 4), (false, false, false)).unwrap();
+        let reciprocal_lattice = [[0.25, 0., 0.], [0., 0.25, 0.], [0., 0., 0.25]];
+        let zone = grid
+            .relocate_to_brillouin_zone(&reciprocal_lattice)
+            .unwrap();
+        // every address component equal to 2 lies on a face of the zone and doubles the images
+        for (i, address) in grid.grid_addresses().iter().enumerate() {
+            let n_faces = address.iter().filter(|&&a| a == 2).count() as u32;
+            assert_eq!(zone.multiplicities[i], 2usize.pow(n_faces));
+            assert_eq!(zone.grid_address[i], *address);
+        }
+        assert_eq!(zone.grid_address.len(), 125);
+        assert_eq!(zone.map.iter().filter(|i| i.is_some()).count(), 125);
+        let flat = [[0.25, 0.25, 0.], [0., 0., 0.], [0., 0., 0.25]];
+        let err = grid.relocate_to_brillouin_zone(&flat).unwrap_err();
+        assert!(matches!(
+            err.kind,
+            SpglibError::InvalidInput(InputError::DegenerateLattice { .. })
+        ));
+        let err = grid
+            .relocate_to_brillouin_zone(&[[f64::NAN; 3]; 3])
+            .unwrap_err();
+        assert!(matches!(
+            err.kind,
+            SpglibError::InvalidInput(InputError::NonFinite)
+        ));
+
+        // the X point (2, 0, 0) is sent to all six face centres of the zone
+        let rotations: Vec<_> = {
+            let lattice = [[4., 0., 0.], [0., 4., 0.], [0., 0., 4.]];
+            let cell = Cell::new(&lattice, &[[0., 0., 0.]], &[1]);
+            let dataset = Dataset::new(&cell, 1e-5).unwrap();
+            dataset.rotations.iter().map(transpose).collect()
+        };
+        let mut images: Vec<[i32; 3]> = zone
+            .grid_points_by_rotations([2, 0, 0], &rotations)
+            .into_iter()
+            .map(|i| zone.grid_address[i.unwrap()])
+            .collect();
+        images.sort_unstable();
+        images.dedup();
+        assert_eq!(
+            images,
+            vec![
+                [-2, 0, 0],
+                [0, -2, 0],
+                [0, 0, -2],
+                [0, 0, 2],
+                [0, 2, 0],
+                [2, 0, 0]
+            ]
+        );
+    }
+}
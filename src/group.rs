@@ -0,0 +1,500 @@
+//! Groups of symmetry operations modulo lattice translations.
+
+use std::collections::HashSet;
+
+use crate::dataset::Dataset;
+use crate::spacegroup::SpacegroupOperations;
+use crate::symmetry::SymmetryOperation;
+
+// Largest order of a space group modulo lattice translations, reached by Fm-3m in its conventional cell.
+const MAX_ORDER: usize = 192;
+
+/// Finite group of symmetry operations, in which operations that differ by a lattice translation
+/// are identified.
+///
+/// Centering translations are kept as separate elements, so the group of a conventional cell
+/// contains the translation subgroup of its centering.
+///
+/// # Example
+///
+/// ```
+/// use spglib::group::SpaceGroupOps;
+/// use spglib::spacegroup::Spacegroup;
+///
+/// // P4/mmm
+/// let operations = Spacegroup::from_hall_number(400).unwrap().operations().unwrap();
+/// let group = SpaceGroupOps::from(&operations);
+/// assert_eq!(group.order(), 16);
+/// assert_eq!(group.generators().len(), 3);
+/// assert_eq!(group.conjugacy_classes().len(), 10);
+/// ```
+#[derive(Clone, Debug)]
+pub struct SpaceGroupOps {
+    operations: Vec<SymmetryOperation>,
+    table: Vec<Vec<usize>>,
+}
+
+/// Factor group of a space group by a normal subgroup.
+#[derive(Clone, Debug)]
+pub struct FactorGroup {
+    /// The cosets forming the elements of the factor group, starting with the normal subgroup.
+    pub cosets: Vec<Vec<SymmetryOperation>>,
+    /// Entry `[i][j]` is the index of the coset containing the products of cosets `i` and `j`.
+    pub multiplication_table: Vec<Vec<usize>>,
+}
+
+impl SpaceGroupOps {
+    /// Returns the group formed by a set of operations, or `None` if the set does not contain
+    /// the identity or is not closed under composition.
+    ///
+    /// Duplicates are dropped and translations are wrapped into `[0, 1)`.
+    pub fn new(operations: &[SymmetryOperation]) -> Option<SpaceGroupOps> {
+        let mut unique: Vec<SymmetryOperation> = Vec::new();
+        for operation in operations.iter().map(normalized) {
+            if !unique.contains(&operation) {
+                unique.push(operation);
+            }
+        }
+        if !unique.contains(&SymmetryOperation::identity()) {
+            return None;
+        }
+        let mut table = vec![vec![0; unique.len()]; unique.len()];
+        for (i, a) in unique.iter().enumerate() {
+            for (j, b) in unique.iter().enumerate() {
+                let product = *a * *b;
+                table[i][j] = unique.iter().position(|c| *c == product)?;
+            }
+        }
+        Some(SpaceGroupOps {
+            operations: unique,
+            table,
+        })
+    }
+
+    /// Returns the smallest group containing the given operations, or `None` if it has more than
+    /// 192 elements, which no space group has.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spglib::group::SpaceGroupOps;
+    /// use spglib::symmetry::SymmetryOperation;
+    ///
+    /// let four: SymmetryOperation = "-y,x,z".parse().unwrap();
+    /// let mirror: SymmetryOperation = "x,y,-z".parse().unwrap();
+    /// let group = SpaceGroupOps::generate(&[four, mirror]).unwrap();
+    /// assert_eq!(group.order(), 8);
+    /// ```
+    pub fn generate(generators: &[SymmetryOperation]) -> Option<SpaceGroupOps> {
+        let mut operations = vec![SymmetryOperation::identity()];
+        let mut i = 0;
+        while i < operations.len() {
+            for generator in generators.iter() {
+                let product = normalized(&(operations[i] * *generator));
+                if !operations.contains(&product) {
+                    if operations.len() == MAX_ORDER {
+                        return None;
+                    }
+                    operations.push(product);
+                }
+            }
+            i += 1;
+        }
+        SpaceGroupOps::new(&operations)
+    }
+
+    /// Returns whether a set of operations is closed under composition.
+    pub fn is_closed(operations: &[SymmetryOperation]) -> bool {
+        operations
+            .iter()
+            .all(|a| operations.iter().all(|b| operations.contains(&(*a * *b))))
+    }
+
+    /// Returns the operations of the group, with translations in `[0, 1)`.
+    pub fn operations(&self) -> &[SymmetryOperation] {
+        &self.operations
+    }
+
+    /// Returns the number of operations in the group.
+    pub fn order(&self) -> usize {
+        self.operations.len()
+    }
+
+    /// Returns the index of an operation in `operations`, if it belongs to the group.
+    pub fn position(&self, operation: &SymmetryOperation) -> Option<usize> {
+        self.operations.iter().position(|a| a == operation)
+    }
+
+    /// Returns whether an operation belongs to the group.
+    pub fn contains(&self, operation: &SymmetryOperation) -> bool {
+        self.position(operation).is_some()
+    }
+
+    /// Returns the multiplication table, whose entry `[i][j]` is the index of the product
+    /// `operations[i] * operations[j]`.
+    pub fn multiplication_table(&self) -> &[Vec<usize>] {
+        &self.table
+    }
+
+    /// Returns a generating set of the smallest possible size.
+    pub fn generators(&self) -> Vec<SymmetryOperation> {
+        let mut found: HashSet<Vec<bool>> = HashSet::new();
+        let mut level = vec![(self.generated(&[]), Vec::new())];
+        loop {
+            if let Some((_, generators)) =
+                level.iter().find(|(members, _)| members.iter().all(|&m| m))
+            {
+                return generators.iter().map(|&i| self.operations[i]).collect();
+            }
+            let mut next = Vec::new();
+            for (members, generators) in level.iter() {
+                for (g, _) in members.iter().enumerate().filter(|(_, &m)| !m) {
+                    let mut extended = generators.clone();
+                    extended.push(g);
+                    let subgroup = self.generated(&extended);
+                    if found.insert(subgroup.clone()) {
+                        next.push((subgroup, extended));
+                    }
+                }
+            }
+            level = next;
+        }
+    }
+
+    /// Returns the conjugacy classes of the group, starting with the class of the identity.
+    pub fn conjugacy_classes(&self) -> Vec<Vec<SymmetryOperation>> {
+        let n = self.order();
+        let inverses = self.inverses();
+        let mut assigned = vec![false; n];
+        let mut classes = Vec::new();
+        for x in self.identity_first() {
+            if assigned[x] {
+                continue;
+            }
+            let mut class = Vec::new();
+            for (row, &inverse) in self.table.iter().zip(inverses.iter()) {
+                let conjugate = self.table[row[x]][inverse];
+                if !assigned[conjugate] {
+                    assigned[conjugate] = true;
+                    class.push(self.operations[conjugate]);
+                }
+            }
+            classes.push(class);
+        }
+        classes
+    }
+
+    /// Returns whether every operation of `subgroup` belongs to the group.
+    pub fn is_subgroup(&self, subgroup: &SpaceGroupOps) -> bool {
+        subgroup.operations.iter().all(|a| self.contains(a))
+    }
+
+    /// Returns whether `subgroup` is a subgroup that is invariant under conjugation.
+    pub fn is_normal_subgroup(&self, subgroup: &SpaceGroupOps) -> bool {
+        self.is_subgroup(subgroup)
+            && self.operations.iter().all(|g| {
+                let inverse = g.inverse();
+                subgroup
+                    .operations
+                    .iter()
+                    .all(|h| subgroup.contains(&(*g * *h * inverse)))
+            })
+    }
+
+    /// Returns the left cosets `gH` of a subgroup `H`, starting with `H` itself,
+    /// or `None` if it is not a subgroup.
+    pub fn left_cosets(&self, subgroup: &SpaceGroupOps) -> Option<Vec<Vec<SymmetryOperation>>> {
+        self.cosets(subgroup, true)
+    }
+
+    /// Returns the right cosets `Hg` of a subgroup `H`, starting with `H` itself,
+    /// or `None` if it is not a subgroup.
+    pub fn right_cosets(&self, subgroup: &SpaceGroupOps) -> Option<Vec<Vec<SymmetryOperation>>> {
+        self.cosets(subgroup, false)
+    }
+
+    /// Returns all subgroups, ordered by the size of their smallest generating set.
+    pub fn subgroups(&self) -> Vec<SpaceGroupOps> {
+        let mut found: HashSet<Vec<bool>> = HashSet::new();
+        let trivial = self.generated(&[]);
+        found.insert(trivial.clone());
+        let mut subgroups = vec![trivial.clone()];
+        let mut level = vec![(trivial, Vec::new())];
+        while !level.is_empty() {
+            let mut next = Vec::new();
+            for (members, generators) in level.iter() {
+                for (g, _) in members.iter().enumerate().filter(|(_, &m)| !m) {
+                    let mut extended = generators.clone();
+                    extended.push(g);
+                    let subgroup = self.generated(&extended);
+                    if found.insert(subgroup.clone()) {
+                        subgroups.push(subgroup.clone());
+                        next.push((subgroup, extended));
+                    }
+                }
+            }
+            level = next;
+        }
+        subgroups
+            .iter()
+            .map(|members| self.subgroup(members))
+            .collect()
+    }
+
+    /// Returns all normal subgroups.
+    pub fn normal_subgroups(&self) -> Vec<SpaceGroupOps> {
+        self.subgroups()
+            .into_iter()
+            .filter(|subgroup| self.is_normal_subgroup(subgroup))
+            .collect()
+    }
+
+    /// Returns the subgroup of pure translations, i.e. the identity and any centering translations.
+    pub fn translation_subgroup(&self) -> SpaceGroupOps {
+        let identity = SymmetryOperation::identity().rotation;
+        let members: Vec<bool> = self
+            .operations
+            .iter()
+            .map(|a| a.rotation == identity)
+            .collect();
+        self.subgroup(&members)
+    }
+
+    /// Returns the factor group by the translation subgroup, which is isomorphic to the point group.
+    pub fn factor_group(&self) -> FactorGroup {
+        let translations = self.translation_subgroup();
+        let cosets = self
+            .left_cosets(&translations)
+            .expect("the translations form a subgroup");
+        let coset_of = |a: &SymmetryOperation| {
+            cosets
+                .iter()
+                .position(|coset| coset.contains(a))
+                .expect("the cosets cover the group")
+        };
+        let multiplication_table = cosets
+            .iter()
+            .map(|a| cosets.iter().map(|b| coset_of(&(a[0] * b[0]))).collect())
+            .collect();
+        FactorGroup {
+            cosets,
+            multiplication_table,
+        }
+    }
+
+    // Returns the members of the subgroup generated by the operations with the given indices.
+    fn generated(&self, generators: &[usize]) -> Vec<bool> {
+        let identity = self.identity_first()[0];
+        let mut members = vec![false; self.order()];
+        members[identity] = true;
+        let mut queue = vec![identity];
+        while let Some(a) = queue.pop() {
+            for &g in generators.iter() {
+                let product = self.table[a][g];
+                if !members[product] {
+                    members[product] = true;
+                    queue.push(product);
+                }
+            }
+        }
+        members
+    }
+
+    // Returns the subgroup with the given members, reusing the multiplication table.
+    fn subgroup(&self, members: &[bool]) -> SpaceGroupOps {
+        let indices: Vec<usize> = (0..self.order()).filter(|&i| members[i]).collect();
+        let table = indices
+            .iter()
+            .map(|&i| {
+                indices
+                    .iter()
+                    .map(|&j| {
+                        let product = self.table[i][j];
+                        indices
+                            .iter()
+                            .position(|&k| k == product)
+                            .expect("subgroups are closed")
+                    })
+                    .collect()
+            })
+            .collect();
+        SpaceGroupOps {
+            operations: indices.iter().map(|&i| self.operations[i]).collect(),
+            table,
+        }
+    }
+
+    fn cosets(&self, subgroup: &SpaceGroupOps, left: bool) -> Option<Vec<Vec<SymmetryOperation>>> {
+        if !self.is_subgroup(subgroup) {
+            return None;
+        }
+        let mut assigned = vec![false; self.order()];
+        let mut cosets = Vec::new();
+        for g in self.identity_first() {
+            if assigned[g] {
+                continue;
+            }
+            let coset: Vec<SymmetryOperation> = subgroup
+                .operations
+                .iter()
+                .map(|h| {
+                    let a = self.operations[g];
+                    normalized(&if left { a * *h } else { *h * a })
+                })
+                .collect();
+            for a in coset.iter() {
+                assigned[self.position(a).expect("the group is closed")] = true;
+            }
+            cosets.push(coset);
+        }
+        Some(cosets)
+    }
+
+    // Returns the indices of the operations with the identity moved to the front.
+    fn identity_first(&self) -> Vec<usize> {
+        let identity = self
+            .position(&SymmetryOperation::identity())
+            .expect("groups contain the identity");
+        let mut indices: Vec<usize> = (0..self.order()).filter(|&i| i != identity).collect();
+        indices.insert(0, identity);
+        indices
+    }
+
+    // Returns the index of the inverse of each operation.
+    fn inverses(&self) -> Vec<usize> {
+        let identity = self.identity_first()[0];
+        self.table
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .position(|&p| p == identity)
+                    .expect("groups have inverses")
+            })
+            .collect()
+    }
+}
+
+impl From<&Dataset> for SpaceGroupOps {
+    fn from(value: &Dataset) -> Self {
+        let operations: Vec<SymmetryOperation> = value.operations().collect();
+        SpaceGroupOps::new(&operations).expect("the operations of a dataset form a group")
+    }
+}
+
+impl From<&SpacegroupOperations> for SpaceGroupOps {
+    fn from(value: &SpacegroupOperations) -> Self {
+        let operations: Vec<SymmetryOperation> = value
+            .rotations
+            .iter()
+            .zip(value.translations.iter())
+            .map(|(rotation, translation)| SymmetryOperation::new(*rotation, *translation))
+            .collect();
+        SpaceGroupOps::new(&operations).expect("the operations of a setting form a group")
+    }
+}
+
+// Returns the operation with its translation wrapped into [0, 1).
+fn normalized(operation: &SymmetryOperation) -> SymmetryOperation {
+    let translation = operation.translation.map(|x| {
+        let wrapped = x - x.floor();
+        if (wrapped - 1.0).abs() < 1e-6 {
+            0.0
+        } else {
+            wrapped
+        }
+    });
+    SymmetryOperation::new(operation.rotation, translation)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dataset::Dataset;
+    use crate::fixtures::rutile;
+    use crate::group::SpaceGroupOps;
+    use crate::spacegroup::Spacegroup;
+    use crate::symmetry::SymmetryOperation;
+
+    #[test]
+    fn group_of_rutile() {
+        let dataset = Dataset::new(&rutile(), 1e-5).unwrap();
+        let group = SpaceGroupOps::from(&dataset);
+        assert_eq!(group.order(), 16);
+        // every row and column of the table is a permutation
+        for row in group.multiplication_table() {
+            let mut sorted = row.clone();
+            sorted.sort_unstable();
+            assert_eq!(sorted, (0..16).collect::<Vec<usize>>());
+        }
+        let generators = group.generators();
+        assert_eq!(generators.len(), 3);
+        let generated = SpaceGroupOps::generate(&generators).unwrap();
+        assert!(generated.is_subgroup(&group) && group.is_subgroup(&generated));
+        // 4/mmm has ten classes
+        let classes = group.conjugacy_classes();
+        assert_eq!(classes.len(), 10);
+        assert_eq!(classes[0], vec![SymmetryOperation::identity()]);
+        assert_eq!(classes.iter().map(|c| c.len()).sum::<usize>(), 16);
+    }
+
+    #[test]
+    fn subgroups_and_cosets() {
+        let dataset = Dataset::new(&rutile(), 1e-5).unwrap();
+        let group = SpaceGroupOps::from(&dataset);
+        let subgroups = group.subgroups();
+        assert_eq!(subgroups[0].order(), 1);
+        for subgroup in subgroups.iter() {
+            assert_eq!(group.order() % subgroup.order(), 0);
+            let left = group.left_cosets(subgroup).unwrap();
+            let right = group.right_cosets(subgroup).unwrap();
+            assert_eq!(left.len() * subgroup.order(), group.order());
+            assert_eq!(left.len(), right.len());
+            let normal = left
+                .iter()
+                .all(|l| right.iter().any(|r| l.iter().all(|a| r.contains(a))));
+            assert_eq!(normal, group.is_normal_subgroup(subgroup));
+        }
+        let normal = group.normal_subgroups();
+        assert!(normal.iter().any(|n| n.order() == 1));
+        assert!(normal.iter().any(|n| n.order() == 16));
+        let halves = SpaceGroupOps::generate(&["-y+1/2,x+1/2,z+1/2".parse().unwrap()]).unwrap();
+        assert_eq!(halves.order(), 4);
+        assert!(group.left_cosets(&halves).is_some());
+        let foreign = SpaceGroupOps::generate(&["z,x,y".parse().unwrap()]).unwrap();
+        assert!(group.left_cosets(&foreign).is_none());
+    }
+
+    #[test]
+    fn factor_group_by_centering() {
+        // Im-3m in the conventional cell
+        let operations = Spacegroup::from_hall_number(529)
+            .unwrap()
+            .operations()
+            .unwrap();
+        let group = SpaceGroupOps::from(&operations);
+        assert_eq!(group.order(), 96);
+        assert_eq!(group.translation_subgroup().order(), 2);
+        assert!(group.is_normal_subgroup(&group.translation_subgroup()));
+        let factor = group.factor_group();
+        assert_eq!(factor.cosets.len(), 48);
+        assert!(factor.cosets.iter().all(|coset| coset.len() == 2));
+        assert_eq!(
+            factor.multiplication_table[0],
+            (0..48).collect::<Vec<usize>>()
+        );
+    }
+
+    #[test]
+    fn closure() {
+        let operations: Vec<SymmetryOperation> = ["x,y,z", "-y,x,z"]
+            .iter()
+            .map(|s| s.parse().unwrap())
+            .collect();
+        assert!(!SpaceGroupOps::is_closed(&operations));
+        assert!(SpaceGroupOps::new(&operations).is_none());
+        let group = SpaceGroupOps::generate(&operations).unwrap();
+        assert!(SpaceGroupOps::is_closed(group.operations()));
+        // an incommensurate translation generates too many operations
+        let translation: SymmetryOperation = "x+0.001,y,z".parse().unwrap();
+        assert!(SpaceGroupOps::generate(&[translation]).is_none());
+    }
+}
@@ -0,0 +1,359 @@
+//! Parsing of Hall symbols into symmetry operations.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::ParseError;
+use crate::group::SpaceGroupOps;
+use crate::symmetry::SymmetryOperation;
+
+const IDENTITY: [[i32; 3]; 3] = [[1, 0, 0], [0, 1, 0], [0, 0, 1]];
+
+/// Space group setting described by a Hall symbol, e.g. `-P 4n 2n` or `P 61 2 (0 0 -1)`.
+///
+/// The symbol lists a lattice symbol, preceded by `-` for centrosymmetric groups, up to four
+/// generating rotations with their axes and translations, and an optional origin shift in units
+/// of 1/12, following Hall (1981) and its revision in International Tables Vol. B.
+///
+/// # Example
+///
+/// ```
+/// use spglib::hall::HallSymbol;
+///
+/// let symbol: HallSymbol = "-P 4 2 3".parse().unwrap();
+/// assert_eq!(symbol.generators().len(), 4);
+/// assert_eq!(symbol.operations().order(), 48);
+/// ```
+#[derive(Clone, Debug)]
+pub struct HallSymbol {
+    symbol: String,
+    generators: Vec<SymmetryOperation>,
+    operations: SpaceGroupOps,
+}
+
+impl HallSymbol {
+    /// Returns the generators encoded by the symbol: the centering translations, the inversion
+    /// of centrosymmetric groups and the listed rotations, all with the origin shift applied.
+    pub fn generators(&self) -> &[SymmetryOperation] {
+        &self.generators
+    }
+
+    /// Returns the full set of operations generated by the symbol.
+    pub fn operations(&self) -> &SpaceGroupOps {
+        &self.operations
+    }
+}
+
+impl fmt::Display for HallSymbol {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.symbol)
+    }
+}
+
+impl FromStr for HallSymbol {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (body, shift) = match s.find('(') {
+            Some(i) => (&s[..i], Some(parse_shift(s, &s[i..])?)),
+            None => (s, None),
+        };
+        let mut tokens = body.split_whitespace();
+        let lattice = tokens
+            .next()
+            .ok_or_else(|| ParseError::new(s, "missing lattice symbol"))?;
+        let (centrosymmetric, letter) = match lattice.strip_prefix('-') {
+            Some(letter) => (true, letter),
+            None => (false, lattice),
+        };
+        let mut generators: Vec<SymmetryOperation> = centering_translations(letter)
+            .ok_or_else(|| ParseError::new(s, format!("unknown lattice symbol {:?}", letter)))?
+            .iter()
+            .map(|t| SymmetryOperation::new(IDENTITY, *t))
+            .collect();
+        if centrosymmetric {
+            generators.push(SymmetryOperation::new(
+                [[-1, 0, 0], [0, -1, 0], [0, 0, -1]],
+                [0.0; 3],
+            ));
+        }
+
+        let mut previous: Option<(u32, Axis)> = None;
+        for (i, token) in tokens.enumerate() {
+            if i == 4 {
+                return Err(ParseError::new(s, "expected at most four rotations"));
+            }
+            let rotation = Rotation::parse(token).map_err(|message| ParseError::new(s, message))?;
+            let axis = rotation.axis(i, previous).ok_or_else(|| {
+                ParseError::new(s, format!("cannot infer the axis of {:?}", token))
+            })?;
+            let operation = rotation
+                .operation(axis)
+                .map_err(|message| ParseError::new(s, message))?;
+            generators.push(operation);
+            previous = Some((rotation.order, axis));
+        }
+        if previous.is_none() {
+            return Err(ParseError::new(s, "missing rotations"));
+        }
+
+        if let Some(v) = shift {
+            let shift = SymmetryOperation::new(IDENTITY, v);
+            let inverse = shift.inverse();
+            for g in generators.iter_mut() {
+                *g = shift * *g * inverse;
+            }
+        }
+        let operations = SpaceGroupOps::generate(&generators).ok_or_else(|| {
+            ParseError::new(
+                s,
+                "the rotations generate more operations than any space group",
+            )
+        })?;
+        Ok(HallSymbol {
+            symbol: s.split_whitespace().collect::<Vec<&str>>().join(" "),
+            generators,
+            operations,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Axis {
+    X,
+    Y,
+    Z,
+    // Face diagonals relative to the preceding axis: ' is e.g. a-b and " is a+b for a c axis.
+    Prime(usize),
+    DoublePrime(usize),
+    BodyDiagonal,
+}
+
+#[derive(Debug)]
+struct Rotation {
+    improper: bool,
+    order: u32,
+    axis: Option<Axis>,
+    screw: u32,
+    translation: [f64; 3],
+}
+
+impl Rotation {
+    // Parses a matrix symbol such as `-4`, `61`, `2xb` or `2"`, leaving the axis unresolved for
+    // the ' and " symbols, which depend on the preceding rotation.
+    fn parse(token: &str) -> Result<Rotation, String> {
+        let (improper, rest) = match token.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, token),
+        };
+        let mut chars = rest.chars();
+        let order = match chars.next() {
+            Some(c @ ('1' | '2' | '3' | '4' | '6')) => c.to_digit(10).unwrap_or(1),
+            _ => return Err(format!("expected a rotation order in {:?}", token)),
+        };
+        let mut rotation = Rotation {
+            improper,
+            order,
+            axis: None,
+            screw: 0,
+            translation: [0.0; 3],
+        };
+        let mut prime: Option<Axis> = None;
+        for c in chars {
+            let t = &mut rotation.translation;
+            match c {
+                'x' => rotation.axis = Some(Axis::X),
+                'y' => rotation.axis = Some(Axis::Y),
+                'z' => rotation.axis = Some(Axis::Z),
+                '\'' => prime = Some(Axis::Prime(0)),
+                '"' => prime = Some(Axis::DoublePrime(0)),
+                '*' => rotation.axis = Some(Axis::BodyDiagonal),
+                '1'..='5' => rotation.screw = c.to_digit(10).unwrap_or(0),
+                'a' => t[0] += 0.5,
+                'b' => t[1] += 0.5,
+                'c' => t[2] += 0.5,
+                'n' => t.iter_mut().for_each(|x| *x += 0.5),
+                'u' => t[0] += 0.25,
+                'v' => t[1] += 0.25,
+                'w' => t[2] += 0.25,
+                'd' => t.iter_mut().for_each(|x| *x += 0.25),
+                _ => return Err(format!("unexpected {:?} in {:?}", c, token)),
+            }
+        }
+        if prime.is_some() {
+            if rotation.axis.is_some() {
+                return Err(format!("conflicting axes in {:?}", token));
+            }
+            rotation.axis = prime;
+        }
+        Ok(rotation)
+    }
+
+    // Resolves the axis of the `index`th rotation using the default rules of Hall symbols.
+    fn axis(&self, index: usize, previous: Option<(u32, Axis)>) -> Option<Axis> {
+        let reference = |previous: Option<(u32, Axis)>| match previous.map(|(_, a)| a) {
+            Some(Axis::X) => Some(0),
+            Some(Axis::Y) => Some(1),
+            // the 2-fold axes following a 3-fold axis along a+b+c are along a-b
+            Some(Axis::Z) | Some(Axis::BodyDiagonal) | None => Some(2),
+            _ => None,
+        };
+        match self.axis {
+            Some(Axis::Prime(_)) => reference(previous).map(Axis::Prime),
+            Some(Axis::DoublePrime(_)) => reference(previous).map(Axis::DoublePrime),
+            Some(axis) => Some(axis),
+            None => match (index, self.order, previous) {
+                (_, 1, _) | (0, _, _) => Some(Axis::Z),
+                (1, 2, Some((2, _))) | (1, 2, Some((4, _))) => Some(Axis::X),
+                (1, 2, Some((3, _))) | (1, 2, Some((6, _))) => reference(previous).map(Axis::Prime),
+                (2, 3, _) => Some(Axis::BodyDiagonal),
+                _ => None,
+            },
+        }
+    }
+
+    fn operation(&self, axis: Axis) -> Result<SymmetryOperation, String> {
+        let mut rotation = rotation_matrix(self.order, axis)
+            .ok_or_else(|| format!("no {}-fold rotation about this axis", self.order))?;
+        if self.improper {
+            rotation.iter_mut().flatten().for_each(|x| *x = -*x);
+        }
+        let mut translation = self.translation;
+        if self.screw > 0 {
+            let direction = match axis {
+                Axis::X => 0,
+                Axis::Y => 1,
+                Axis::Z => 2,
+                _ => return Err("screw translations need a principal axis".to_string()),
+            };
+            if self.screw >= self.order {
+                return Err(format!("invalid screw {}{}", self.order, self.screw));
+            }
+            translation[direction] += self.screw as f64 / self.order as f64;
+        }
+        Ok(SymmetryOperation::new(rotation, translation))
+    }
+}
+
+// Returns the rotation of the given order about an axis, as listed in Table A1.4.2.4 of
+// International Tables Vol. B.
+fn rotation_matrix(order: u32, axis: Axis) -> Option<[[i32; 3]; 3]> {
+    let rotation = match (order, axis) {
+        (1, _) => IDENTITY,
+        (2, Axis::X) => [[1, 0, 0], [0, -1, 0], [0, 0, -1]],
+        (3, Axis::X) => [[1, 0, 0], [0, 0, -1], [0, 1, -1]],
+        (4, Axis::X) => [[1, 0, 0], [0, 0, -1], [0, 1, 0]],
+        (6, Axis::X) => [[1, 0, 0], [0, 1, -1], [0, 1, 0]],
+        (2, Axis::Y) => [[-1, 0, 0], [0, 1, 0], [0, 0, -1]],
+        (3, Axis::Y) => [[-1, 0, 1], [0, 1, 0], [-1, 0, 0]],
+        (4, Axis::Y) => [[0, 0, 1], [0, 1, 0], [-1, 0, 0]],
+        (6, Axis::Y) => [[0, 0, 1], [0, 1, 0], [-1, 0, 1]],
+        (2, Axis::Z) => [[-1, 0, 0], [0, -1, 0], [0, 0, 1]],
+        (3, Axis::Z) => [[0, -1, 0], [1, -1, 0], [0, 0, 1]],
+        (4, Axis::Z) => [[0, -1, 0], [1, 0, 0], [0, 0, 1]],
+        (6, Axis::Z) => [[1, -1, 0], [1, 0, 0], [0, 0, 1]],
+        (2, Axis::Prime(0)) => [[-1, 0, 0], [0, 0, -1], [0, -1, 0]],
+        (2, Axis::Prime(1)) => [[0, 0, -1], [0, -1, 0], [-1, 0, 0]],
+        (2, Axis::Prime(2)) => [[0, -1, 0], [-1, 0, 0], [0, 0, -1]],
+        (2, Axis::DoublePrime(0)) => [[-1, 0, 0], [0, 0, 1], [0, 1, 0]],
+        (2, Axis::DoublePrime(1)) => [[0, 0, 1], [0, -1, 0], [1, 0, 0]],
+        (2, Axis::DoublePrime(2)) => [[0, 1, 0], [1, 0, 0], [0, 0, -1]],
+        (3, Axis::BodyDiagonal) => [[0, 0, 1], [1, 0, 0], [0, 1, 0]],
+        _ => return None,
+    };
+    Some(rotation)
+}
+
+// Returns the centering translations of a lattice symbol, excluding the zero translation.
+fn centering_translations(letter: &str) -> Option<Vec<[f64; 3]>> {
+    let third = 1.0 / 3.0;
+    let translations = match letter {
+        "P" => vec![],
+        "A" => vec![[0.0, 0.5, 0.5]],
+        "B" => vec![[0.5, 0.0, 0.5]],
+        "C" => vec![[0.5, 0.5, 0.0]],
+        "I" => vec![[0.5, 0.5, 0.5]],
+        "R" => vec![
+            [2.0 * third, third, third],
+            [third, 2.0 * third, 2.0 * third],
+        ],
+        "S" => vec![
+            [third, third, 2.0 * third],
+            [2.0 * third, 2.0 * third, third],
+        ],
+        "T" => vec![
+            [third, 2.0 * third, third],
+            [2.0 * third, third, 2.0 * third],
+        ],
+        "F" => vec![[0.0, 0.5, 0.5], [0.5, 0.0, 0.5], [0.5, 0.5, 0.0]],
+        _ => return None,
+    };
+    Some(translations)
+}
+
+// Parses an origin shift such as `(0 0 -1)`, given in twelfths.
+fn parse_shift(s: &str, shift: &str) -> Result<[f64; 3], ParseError> {
+    let inner = shift
+        .trim()
+        .strip_prefix('(')
+        .and_then(|t| t.strip_suffix(')'))
+        .ok_or_else(|| ParseError::new(s, "origin shift must be enclosed in parentheses"))?;
+    let components = inner
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|c| !c.is_empty())
+        .map(|c| c.parse::<i32>())
+        .collect::<Result<Vec<i32>, _>>()
+        .map_err(|_| ParseError::new(s, "origin shift must consist of integers"))?;
+    match components.as_slice() {
+        [x, y, z] => Ok([*x as f64 / 12.0, *y as f64 / 12.0, *z as f64 / 12.0]),
+        _ => Err(ParseError::new(
+            s,
+            "origin shift must have three components",
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::group::SpaceGroupOps;
+    use crate::hall::HallSymbol;
+    use crate::spacegroup::Spacegroup;
+
+    #[test]
+    fn hall_symbols_match_database() {
+        for spacegroup in Spacegroup::all_settings() {
+            let symbol: HallSymbol = spacegroup.hall_symbol.parse().unwrap();
+            let parsed = symbol.operations();
+            let database = SpaceGroupOps::from(&spacegroup.operations().unwrap());
+            assert_eq!(
+                parsed.order(),
+                database.order(),
+                "{}",
+                spacegroup.hall_symbol
+            );
+            assert!(parsed.is_subgroup(&database), "{}", spacegroup.hall_symbol);
+        }
+    }
+
+    #[test]
+    fn hall_symbol_syntax() {
+        let symbol: HallSymbol = "  P 61   2 (0 0 -1) ".parse().unwrap();
+        assert_eq!(symbol.to_string(), "P 61 2 (0 0 -1)");
+        assert_eq!(symbol.operations().order(), 12);
+        // the second 2-fold axis of a hexagonal group defaults to a-b
+        let symbol: HallSymbol = "P 6 2".parse().unwrap();
+        assert!(symbol.operations().contains(&"-y,-x,-z".parse().unwrap()));
+        assert!("".parse::<HallSymbol>().is_err());
+        assert!("P".parse::<HallSymbol>().is_err());
+        assert!("Q 2".parse::<HallSymbol>().is_err());
+        assert!("P 5".parse::<HallSymbol>().is_err());
+        assert!("P 2 2 2 2 2".parse::<HallSymbol>().is_err());
+        assert!("P 2q".parse::<HallSymbol>().is_err());
+        assert!("P 2 (0 0)".parse::<HallSymbol>().is_err());
+        assert!("P 2 (0 0 1".parse::<HallSymbol>().is_err());
+        assert!("P 2 6".parse::<HallSymbol>().is_err());
+        // a 4-fold axis perpendicular to a 3- or 6-fold axis generates an infinite group
+        assert!("P 6 4x".parse::<HallSymbol>().is_err());
+        assert!("P 3 4x".parse::<HallSymbol>().is_err());
+    }
+}
@@ -0,0 +1,143 @@
+//! Lattice parameters and their conversion to and from lattice vectors.
+
+use crate::math::{column, dot};
+
+// Smallest squared volume of a cell with unit edges that is not considered flat.
+const VOLUME_TOLERANCE: f64 = 1e-12;
+
+/// Lengths of the three lattice vectors and the angles between them.
+///
+/// `alpha` is the angle between `b` and `c`, `beta` between `a` and `c` and `gamma` between
+/// `a` and `b`, all in degrees.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LatticeParameters {
+    /// Length of the first lattice vector.
+    pub a: f64,
+    /// Length of the second lattice vector.
+    pub b: f64,
+    /// Length of the third lattice vector.
+    pub c: f64,
+    /// Angle between the second and third lattice vectors in degrees.
+    pub alpha: f64,
+    /// Angle between the first and third lattice vectors in degrees.
+    pub beta: f64,
+    /// Angle between the first and second lattice vectors in degrees.
+    pub gamma: f64,
+}
+
+impl LatticeParameters {
+    /// Returns new lattice parameters, with the angles in degrees.
+    pub fn new(a: f64, b: f64, c: f64, alpha: f64, beta: f64, gamma: f64) -> LatticeParameters {
+        LatticeParameters {
+            a,
+            b,
+            c,
+            alpha,
+            beta,
+            gamma,
+        }
+    }
+
+    /// Returns the parameters of a lattice whose columns are the lattice vectors.
+    pub fn from_lattice(lattice: &[[f64; 3]; 3]) -> LatticeParameters {
+        let vectors = [0, 1, 2].map(|j| column(lattice, j));
+        let lengths = vectors.map(|v| dot(&v, &v).sqrt());
+        let angle = |i: usize, j: usize| {
+            (dot(&vectors[i], &vectors[j]) / (lengths[i] * lengths[j]))
+                .clamp(-1.0, 1.0)
+                .acos()
+                .to_degrees()
+        };
+        LatticeParameters::new(
+            lengths[0],
+            lengths[1],
+            lengths[2],
+            angle(1, 2),
+            angle(0, 2),
+            angle(0, 1),
+        )
+    }
+
+    /// Returns the lattice vectors as the columns of a matrix, in the standard orientation with `a`
+    /// along x and `b` in the xy plane, or `None` if the parameters do not describe a cell with
+    /// positive volume.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spglib::lattice::LatticeParameters;
+    ///
+    /// let hexagonal = LatticeParameters::new(3.0, 3.0, 5.0, 90.0, 90.0, 120.0);
+    /// let lattice = hexagonal.lattice().unwrap();
+    /// assert_eq!(lattice[0][0], 3.0);
+    /// assert!((lattice[0][1] + 1.5).abs() < 1e-12);
+    /// assert!(LatticeParameters::new(1.0, 1.0, 1.0, 90.0, 90.0, 180.0).lattice().is_none());
+    /// ```
+    pub fn lattice(&self) -> Option<[[f64; 3]; 3]> {
+        if !(self.a > 0.0 && self.b > 0.0 && self.c > 0.0) {
+            return None;
+        }
+        let [cos_alpha, cos_beta, cos_gamma] = [self.alpha, self.beta, self.gamma].map(cos_degrees);
+        // squared volume of the cell with unit edges
+        let volume2 = 1.0 - cos_alpha * cos_alpha - cos_beta * cos_beta - cos_gamma * cos_gamma
+            + 2.0 * cos_alpha * cos_beta * cos_gamma;
+        if volume2.is_nan() || volume2 <= VOLUME_TOLERANCE {
+            return None;
+        }
+        let sin_gamma = (1.0 - cos_gamma * cos_gamma).sqrt();
+        let cy = (cos_alpha - cos_beta * cos_gamma) / sin_gamma;
+        let cz2 = 1.0 - cos_beta * cos_beta - cy * cy;
+        Some([
+            [self.a, self.b * cos_gamma, self.c * cos_beta],
+            [0.0, self.b * sin_gamma, self.c * cy],
+            [0.0, 0.0, self.c * cz2.sqrt()],
+        ])
+    }
+}
+
+// Returns the cosine of an angle in degrees, exactly zero for right angles.
+fn cos_degrees(angle: f64) -> f64 {
+    let cos = angle.to_radians().cos();
+    if cos.abs() < 1e-12 {
+        0.0
+    } else {
+        cos
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lattice::LatticeParameters;
+
+    #[test]
+    fn parameters_round_trip() {
+        let parameters = LatticeParameters::new(3.0, 4.0, 5.0, 70.0, 80.0, 100.0);
+        let lattice = parameters.lattice().unwrap();
+        assert_eq!(lattice[1][0], 0.0);
+        assert_eq!(lattice[2][0], 0.0);
+        assert_eq!(lattice[2][1], 0.0);
+        let found = LatticeParameters::from_lattice(&lattice);
+        for (x, y) in [
+            (found.a, 3.0),
+            (found.b, 4.0),
+            (found.c, 5.0),
+            (found.alpha, 70.0),
+            (found.beta, 80.0),
+            (found.gamma, 100.0),
+        ] {
+            assert!((x - y).abs() < 1e-10);
+        }
+        let cubic = LatticeParameters::new(4.0, 4.0, 4.0, 90.0, 90.0, 90.0);
+        assert_eq!(
+            cubic.lattice().unwrap(),
+            [[4.0, 0.0, 0.0], [0.0, 4.0, 0.0], [0.0, 0.0, 4.0]]
+        );
+        // the angles violate the triangle inequality of a spherical triangle
+        assert!(LatticeParameters::new(1.0, 1.0, 1.0, 30.0, 30.0, 90.0)
+            .lattice()
+            .is_none());
+        assert!(LatticeParameters::new(0.0, 1.0, 1.0, 90.0, 90.0, 90.0)
+            .lattice()
+            .is_none());
+    }
+}
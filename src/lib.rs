@@ -4,12 +4,33 @@
 #![deny(missing_debug_implementations)]
 
 pub mod cell;
+pub mod crystal;
 pub mod dataset;
+pub mod element;
 pub mod error;
+#[cfg(test)]
+mod fixtures;
+pub mod grid;
+pub mod group;
+pub mod hall;
+pub mod lattice;
+mod math;
+pub mod mesh;
+mod notation;
+pub mod pointgroup;
 pub mod spacegroup;
+pub mod symmetry;
+pub mod tolerance;
+
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
 
 use spglib_sys as ffi;
 
+use crate::error::{Error, InputError, SpglibError};
+use crate::pointgroup::PointGroup;
+use crate::symmetry::check_rotations;
+
 /// Returns the major version of the underlying spglib C library.
 pub fn major_version() -> i32 {
     unsafe { ffi::spg_get_major_version() }
@@ -27,6 +48,10 @@ pub fn micro_version() -> i32 {
 
 /// Returns the hall number for a set of symmetry operations.
 ///
+/// Fails if the numbers of rotations and translations differ, if there are no rotations, if a
+/// rotation does not have determinant 1 or -1, if a translation is not finite, or if spglib
+/// finds no matching setting.
+///
 /// # Example
 ///
 /// Get the hall number for a BCC cell in a roundabout way.
@@ -46,22 +71,101 @@ pub fn micro_version() -> i32 {
 ///     [0.5, 0.5, 0.5]
 /// ];
 /// let types = [1, 1];
-/// let mut cell = Cell::new(&lattice, &positions, &types);
-/// let mut dataset = Dataset::new(&mut cell, 1.0e-6);
-/// let hall_number = hall_number_from_symmetry(&mut dataset.rotations, &mut dataset.translations, 1.0e-6);
-/// assert_eq!(hall_number, dataset.hall_number);
+/// let cell = Cell::new(&lattice, &positions, &types);
+/// let dataset = Dataset::new(&cell, 1.0e-6).unwrap();
+/// let hall_number = hall_number_from_symmetry(&dataset.rotations, &dataset.translations, 1.0e-6);
+/// assert_eq!(hall_number.unwrap(), dataset.hall_number);
+/// assert!(hall_number_from_symmetry(&dataset.rotations, &dataset.translations[1..], 1.0e-6).is_err());
 /// ```
 pub fn hall_number_from_symmetry(
-    rotations: &mut [[[i32; 3]; 3]],
-    translations: &mut [[f64; 3]],
+    rotations: &[[[i32; 3]; 3]],
+    translations: &[[f64; 3]],
     symprec: f64,
-) -> i32 {
-    unsafe {
+) -> Result<i32, Error> {
+    let function = "spg_get_hall_number_from_symmetry";
+    if rotations.len() != translations.len() {
+        let err = InputError::LengthMismatch {
+            rotations: rotations.len(),
+            translations: translations.len(),
+        };
+        return Err(Error::invalid_input(err, function));
+    }
+    check_rotations(rotations).map_err(|err| Error::invalid_input(err, function))?;
+    if !translations.iter().flatten().all(|x| x.is_finite()) {
+        return Err(Error::invalid_input(InputError::NonFinite, function));
+    }
+    let hall_number = unsafe {
         ffi::spg_get_hall_number_from_symmetry(
             rotations.as_ptr() as *mut [[i32; 3]; 3],
             translations.as_ptr() as *mut [f64; 3],
             rotations.len() as i32,
             symprec,
         )
+    };
+    if hall_number == 0 {
+        return Err(Error::new(SpglibError::SpacegroupSearchFailed, function).with_symprec(symprec));
+    }
+    Ok(hall_number)
+}
+
+/// Returns the point group of a set of rotations along with the transformation matrix to its standard frame.
+/// Refer to the full documentation of the implementation [here](https://spglib.github.io/spglib/api.html#spg-get-pointgroup).
+///
+/// Fails if there are no rotations or a rotation does not have determinant 1 or -1.
+///
+/// # Example
+///
+/// Get the point group of a BCC cell from its rotations.
+///
+/// ```
+/// use spglib::pointgroup_from_rotations;
+/// use spglib::cell::Cell;
+/// use spglib::dataset::Dataset;
+/// use spglib::pointgroup::PointGroup;
+///
+/// let lattice = [
+///     [4.0, 0.0, 0.0],
+///     [0.0, 4.0, 0.0],
+///     [0.0, 0.0, 4.0]
+/// ];
+/// let positions = [
+///     [0.0, 0.0, 0.0],
+///     [0.5, 0.5, 0.5]
+/// ];
+/// let types = [1, 1];
+/// let cell = Cell::new(&lattice, &positions, &types);
+/// let dataset = Dataset::new(&cell, 1.0e-6).unwrap();
+/// let (pointgroup, transformation_matrix) = pointgroup_from_rotations(&dataset.rotations).unwrap();
+/// assert_eq!(pointgroup, PointGroup::Oh);
+/// assert_eq!(pointgroup.international(), dataset.pointgroup_symbol);
+/// assert_eq!(transformation_matrix, [[1, 0, 0], [0, 1, 0], [0, 0, 1]]);
+/// ```
+pub fn pointgroup_from_rotations(
+    rotations: &[[[i32; 3]; 3]],
+) -> Result<(PointGroup, [[i32; 3]; 3]), Error> {
+    let function = "spg_get_pointgroup";
+    check_rotations(rotations).map_err(|err| Error::invalid_input(err, function))?;
+    let mut symbol: [c_char; 6] = [0; 6];
+    let mut transformation_matrix = [[0; 3]; 3];
+    let number = unsafe {
+        ffi::spg_get_pointgroup(
+            symbol.as_mut_ptr(),
+            transformation_matrix.as_mut_ptr(),
+            rotations.as_ptr() as *mut [[i32; 3]; 3],
+            rotations.len() as i32,
+        )
+    };
+    if number == 0 {
+        return Err(Error::last(function));
+    }
+    let symbol = match CString::from(unsafe { CStr::from_ptr(symbol.as_ptr()) }).to_str() {
+        Ok(s) => String::from(s),
+        Err(_) => return Err(Error::new(SpglibError::Unknown, function)),
+    };
+    match PointGroup::from_number(number) {
+        Some(pointgroup) if pointgroup.international() == symbol => {
+            Ok((pointgroup, transformation_matrix))
+        }
+        _ => Err(Error::new(SpglibError::PointgroupNotFound, function)),
     }
 }
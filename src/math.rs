@@ -0,0 +1,110 @@
+//! Small fixed-size matrix helpers.
+
+// Returns the product of a matrix and a column vector.
+pub(crate) fn mat_vec(m: &[[f64; 3]; 3], v: &[f64; 3]) -> [f64; 3] {
+    let mut res = [0.0; 3];
+    for (i, row) in m.iter().enumerate() {
+        res[i] = row[0] * v[0] + row[1] * v[1] + row[2] * v[2];
+    }
+    res
+}
+
+// Returns the inverse of a matrix or `None` if it is singular.
+pub(crate) fn inverse(m: &[[f64; 3]; 3]) -> Option<[[f64; 3]; 3]> {
+    let det = det(m);
+    if det == 0.0 {
+        return None;
+    }
+    let mut res = [[0.0; 3]; 3];
+    for (i, row) in res.iter_mut().enumerate() {
+        for (j, x) in row.iter_mut().enumerate() {
+            let (a, b) = ((j + 1) % 3, (j + 2) % 3);
+            let (c, d) = ((i + 1) % 3, (i + 2) % 3);
+            *x = (m[a][c] * m[b][d] - m[a][d] * m[b][c]) / det;
+        }
+    }
+    Some(res)
+}
+
+// Returns the product of two integer matrices.
+pub(crate) fn mat_mul_i(a: &[[i32; 3]; 3], b: &[[i32; 3]; 3]) -> [[i32; 3]; 3] {
+    let mut res = [[0; 3]; 3];
+    for (i, row) in res.iter_mut().enumerate() {
+        for (j, x) in row.iter_mut().enumerate() {
+            *x = a[i][0] * b[0][j] + a[i][1] * b[1][j] + a[i][2] * b[2][j];
+        }
+    }
+    res
+}
+
+// Returns the product of an integer matrix and a column vector.
+pub(crate) fn mat_vec_i(m: &[[i32; 3]; 3], v: &[f64; 3]) -> [f64; 3] {
+    let mut res = [0.0; 3];
+    for (i, row) in m.iter().enumerate() {
+        res[i] = row[0] as f64 * v[0] + row[1] as f64 * v[1] + row[2] as f64 * v[2];
+    }
+    res
+}
+
+// Returns the determinant of an integer matrix.
+pub(crate) fn det_i(m: &[[i32; 3]; 3]) -> i32 {
+    m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+        - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+        + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+}
+
+// Returns the inverse of an integer matrix or `None` if it is not unimodular.
+pub(crate) fn inverse_i(m: &[[i32; 3]; 3]) -> Option<[[i32; 3]; 3]> {
+    let det = det_i(m);
+    if det.abs() != 1 {
+        return None;
+    }
+    let mut res = [[0; 3]; 3];
+    for (i, row) in res.iter_mut().enumerate() {
+        for (j, x) in row.iter_mut().enumerate() {
+            let (a, b) = ((j + 1) % 3, (j + 2) % 3);
+            let (c, d) = ((i + 1) % 3, (i + 2) % 3);
+            *x = (m[a][c] * m[b][d] - m[a][d] * m[b][c]) * det;
+        }
+    }
+    Some(res)
+}
+
+// Returns the transpose of an integer matrix.
+pub(crate) fn transpose_i(m: &[[i32; 3]; 3]) -> [[i32; 3]; 3] {
+    let mut res = [[0; 3]; 3];
+    for (i, row) in res.iter_mut().enumerate() {
+        for (j, x) in row.iter_mut().enumerate() {
+            *x = m[j][i];
+        }
+    }
+    res
+}
+
+// Returns the determinant of a matrix.
+pub(crate) fn det(m: &[[f64; 3]; 3]) -> f64 {
+    m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+        - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+        + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+}
+
+// Returns the transpose of a matrix.
+pub(crate) fn transpose(m: &[[f64; 3]; 3]) -> [[f64; 3]; 3] {
+    let mut res = [[0.0; 3]; 3];
+    for (i, row) in res.iter_mut().enumerate() {
+        for (j, x) in row.iter_mut().enumerate() {
+            *x = m[j][i];
+        }
+    }
+    res
+}
+
+// Returns the `j`th column of a matrix.
+pub(crate) fn column(m: &[[f64; 3]; 3], j: usize) -> [f64; 3] {
+    [m[0][j], m[1][j], m[2][j]]
+}
+
+// Returns the dot product of two vectors.
+pub(crate) fn dot(a: &[f64; 3], b: &[f64; 3]) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
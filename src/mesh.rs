@@ -0,0 +1,270 @@
+//! Irreducible reciprocal-space meshes.
+
+use std::os::raw::c_int;
+
+use spglib_sys as ffi;
+
+use crate::error::{Error, InputError, SpglibError};
+use crate::symmetry::check_rotations;
+
+// Largest number of grid points of a mesh, which keeps the buffers spglib fills in the hundreds
+// of megabytes.
+const MAX_GRID_POINTS: usize = 1 << 24;
+
+/// Uniform reciprocal-space mesh reduced by the symmetry of a structure.
+///
+/// A grid point with address `a` lies at the reduced coordinates
+/// `(2 * a + s) / (2 * mesh)`, where `s` is 1 along the shifted axes and 0 otherwise.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReciprocalMesh {
+    /// Number of grid points along each reciprocal basis vector.
+    pub mesh: [i32; 3],
+    /// Half-grid shift along each reciprocal basis vector.
+    pub is_shift: [bool; 3],
+    /// Integer addresses of all grid points.
+    pub grid_address: Vec<[i32; 3]>,
+    /// Index of the irreducible grid point each grid point is mapped to.
+    pub mapping: Vec<usize>,
+    /// Indices of the irreducible grid points.
+    pub ir_grid_points: Vec<usize>,
+    /// Number of grid points mapped to each irreducible grid point.
+    pub weights: Vec<usize>,
+}
+
+impl ReciprocalMesh {
+    /// Returns the irreducible points of a uniform mesh under the given rotations,
+    /// keeping only the rotations that leave every one of `qpoints` invariant.
+    /// Refer to the full documentation of the implementation [here](https://spglib.github.io/spglib/api.html#spg-get-stabilized-reciprocal-mesh).
+    ///
+    /// The rotations are given in real space and reduced coordinates, as in `Dataset::rotations`.
+    /// The q-points are given in reduced coordinates of the reciprocal lattice.
+    ///
+    /// # Example
+    ///
+    /// Reduce a 4x4x4 mesh of a simple cubic cell by the little group of the X point.
+    ///
+    /// ```
+    /// use spglib::cell::Cell;
+    /// use spglib::dataset::Dataset;
+    /// use spglib::mesh::ReciprocalMesh;
+    ///
+    /// let lattice = [[4., 0., 0.], [0., 4., 0.], [0., 0., 4.]];
+    /// let cell = Cell::new(&lattice, &[[0., 0., 0.]], &[1]);
+    /// let dataset = Dataset::new(&cell, 1e-5).unwrap();
+    /// let mesh = ReciprocalMesh::stabilized(
+    ///     (4, 4, 4),
+    ///     (false, false, false),
+    ///     true,
+    ///     &dataset.rotations,
+    ///     &[[0., 0., 0.5]],
+    /// )
+    /// .unwrap();
+    /// assert_eq!(mesh.ir_grid_points.len(), 18);
+    /// ```
+    ///
+    /// Fails with [`SpglibError::InvalidMesh`] if any mesh dimension is not positive or the mesh
+    /// has more than 2^24 points, and with [`SpglibError::InvalidInput`] if there are no
+    /// rotations, a rotation does not have determinant 1 or -1, or a q-point is not finite.
+    pub fn stabilized(
+        mesh: (i32, i32, i32),
+        shift: (bool, bool, bool),
+        is_time_reversal: bool,
+        rotations: &[[[i32; 3]; 3]],
+        qpoints: &[[f64; 3]],
+    ) -> Result<ReciprocalMesh, Error> {
+        let mesh = [mesh.0, mesh.1, mesh.2];
+        let is_shift = [shift.0, shift.1, shift.2];
+        let function = "spg_get_stabilized_reciprocal_mesh";
+        let n_points =
+            mesh_size(&mesh).ok_or_else(|| Error::new(SpglibError::InvalidMesh(mesh), function))?;
+        check_rotations(rotations).map_err(|err| Error::invalid_input(err, function))?;
+        if !qpoints.iter().flatten().all(|x| x.is_finite()) {
+            return Err(Error::invalid_input(InputError::NonFinite, function));
+        }
+        let raw_shift = is_shift.map(|s| s as c_int);
+        let mut grid_address = vec![[0; 3]; n_points];
+        let mut mapping = vec![0; n_points];
+        let res = unsafe {
+            ffi::spg_get_stabilized_reciprocal_mesh(
+                grid_address.as_mut_ptr(),
+                mapping.as_mut_ptr(),
+                mesh.as_ptr(),
+                raw_shift.as_ptr(),
+                is_time_reversal as c_int,
+                rotations.len() as c_int,
+                rotations.as_ptr() as *mut [[c_int; 3]; 3],
+                qpoints.len() as c_int,
+                qpoints.as_ptr() as *mut [f64; 3],
+            )
+        };
+        if res == 0 {
+            return Err(Error::last(function));
+        }
+        Ok(ReciprocalMesh::from_raw(
+            mesh,
+            is_shift,
+            grid_address,
+            mapping,
+        ))
+    }
+
+    // Collects the irreducible points and weights from a spglib mapping table.
+    pub(crate) fn from_raw(
+        mesh: [i32; 3],
+        is_shift: [bool; 3],
+        grid_address: Vec<[i32; 3]>,
+        mapping: Vec<c_int>,
+    ) -> ReciprocalMesh {
+        let mapping: Vec<usize> = mapping.into_iter().map(|i| i as usize).collect();
+        let mut counts = vec![0; mapping.len()];
+        for &i in mapping.iter() {
+            counts[i] += 1;
+        }
+        let ir_grid_points: Vec<usize> = (0..mapping.len()).filter(|&i| counts[i] > 0).collect();
+        let weights = ir_grid_points.iter().map(|&i| counts[i]).collect();
+        ReciprocalMesh {
+            mesh,
+            is_shift,
+            grid_address,
+            mapping,
+            ir_grid_points,
+            weights,
+        }
+    }
+
+    /// Returns the reduced coordinates of the irreducible grid points.
+    pub fn ir_qpoints(&self) -> Vec<[f64; 3]> {
+        self.ir_grid_points
+            .iter()
+            .map(|&i| {
+                let address = self.grid_address[i];
+                let mut qpoint = [0.0; 3];
+                for k in 0..3 {
+                    let shift = if self.is_shift[k] { 1 } else { 0 };
+                    qpoint[k] = (2 * address[k] + shift) as f64 / (2 * self.mesh[k]) as f64;
+                }
+                qpoint
+            })
+            .collect()
+    }
+}
+
+// Returns the number of points of a mesh, or `None` if a dimension is not positive or the mesh
+// has more than `MAX_GRID_POINTS` points.
+pub(crate) fn mesh_size(mesh: &[i32; 3]) -> Option<usize> {
+    if mesh.iter().any(|&m| m <= 0) {
+        return None;
+    }
+    mesh.iter()
+        .try_fold(1usize, |n, &m| n.checked_mul(m as usize))
+        .filter(|&n| n <= MAX_GRID_POINTS)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cell::Cell;
+    use crate::dataset::Dataset;
+    use crate::error::{InputError, SpglibError};
+    use crate::mesh::ReciprocalMesh;
+
+    #[test]
+    fn stabilized_mesh_matches_ir_mesh() {
+        let lattice = [[4., 0., 0.], [0., 4., 0.], [0., 0., 4.]];
+        let positions = [[0., 0., 0.], [0.5, 0.5, 0.5]];
+        let cell = Cell::new(&lattice, &positions, &[1, 2]);
+        let dataset = Dataset::new(&cell, 1e-5).unwrap();
+        for &shift in [(false, false, false), (true, true, true)].iter() {
+            let expected = cell
+                .ir_reciprocal_mesh((4, 4, 4), shift, true, 1e-5)
+                .unwrap();
+            let mesh =
+                ReciprocalMesh::stabilized((4, 4, 4), shift, true, &dataset.rotations, &[[0.; 3]])
+                    .unwrap();
+            assert_eq!(mesh, expected);
+        }
+    }
+
+    #[test]
+    fn stabilized_mesh_little_group() {
+        let lattice = [[4., 0., 0.], [0., 4., 0.], [0., 0., 4.]];
+        let cell = Cell::new(&lattice, &[[0., 0., 0.]], &[1]);
+        let dataset = Dataset::new(&cell, 1e-5).unwrap();
+        // the little group of X is 4/mmm: six in-plane stars of (x, y) times the stars of z = 0, +-1, 2
+        let mesh = ReciprocalMesh::stabilized(
+            (4, 4, 4),
+            (false, false, false),
+            true,
+            &dataset.rotations,
+            &[[0., 0., 0.5]],
+        )
+        .unwrap();
+        let mut weights = mesh.weights.clone();
+        weights.sort_unstable();
+        assert_eq!(
+            weights,
+            vec![1, 1, 1, 1, 2, 2, 2, 2, 4, 4, 4, 4, 4, 4, 4, 8, 8, 8]
+        );
+        assert_eq!(weights.iter().sum::<usize>(), 64);
+
+        let err = ReciprocalMesh::stabilized(
+            (0, 4, 4),
+            (false, false, false),
+            true,
+            &dataset.rotations,
+            &[[0., 0., 0.5]],
+        )
+        .unwrap_err();
+        assert!(matches!(err.kind, SpglibError::InvalidMesh([0, 4, 4])));
+        let err = ReciprocalMesh::stabilized(
+            (1000, 1000, 1000),
+            (false, false, false),
+            true,
+            &dataset.rotations,
+            &[[0., 0., 0.5]],
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err.kind,
+            SpglibError::InvalidMesh([1000, 1000, 1000])
+        ));
+        let err = ReciprocalMesh::stabilized(
+            (4, 4, 4),
+            (false, false, false),
+            true,
+            &[],
+            &[[0., 0., 0.]],
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err.kind,
+            SpglibError::InvalidInput(InputError::NoRotations)
+        ));
+        let err = ReciprocalMesh::stabilized(
+            (4, 4, 4),
+            (false, false, false),
+            true,
+            &[
+                [[1, 0, 0], [0, 1, 0], [0, 0, 1]],
+                [[2, 0, 0], [0, 1, 0], [0, 0, 1]],
+            ],
+            &[[0., 0., 0.]],
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err.kind,
+            SpglibError::InvalidInput(InputError::NotUnimodular { index: 1 })
+        ));
+        let err = ReciprocalMesh::stabilized(
+            (4, 4, 4),
+            (false, false, false),
+            true,
+            &dataset.rotations,
+            &[[0., f64::NAN, 0.5]],
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err.kind,
+            SpglibError::InvalidInput(InputError::NonFinite)
+        ));
+    }
+}
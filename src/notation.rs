@@ -0,0 +1,201 @@
+//! Formatting of fractions and affine expressions in crystallographic notation.
+
+// Largest denominator recognised when formatting a number as a fraction.
+const MAX_DENOMINATOR: i64 = 48;
+
+// Largest difference between a number and the fraction it is formatted as.
+const FRACTION_TOLERANCE: f64 = 1e-6;
+
+// Returns the numerator and denominator of a fraction close to `x`, if there is one.
+pub(crate) fn fraction(x: f64) -> Option<(i64, i64)> {
+    (1..=MAX_DENOMINATOR).find_map(|den| {
+        let num = (x * den as f64).round();
+        if (x - num / den as f64).abs() < FRACTION_TOLERANCE {
+            Some((num as i64, den))
+        } else {
+            None
+        }
+    })
+}
+
+// Formats a number as an integer or fraction, e.g. `-1/4`, falling back to a decimal.
+pub(crate) fn format_number(x: f64) -> String {
+    match fraction(x) {
+        Some((num, 1)) => format!("{}", num),
+        Some((num, den)) => format!("{}/{}", num, den),
+        None => format!("{}", x),
+    }
+}
+
+// Formats `c[0] x + c[1] y + c[2] z + constant` as e.g. `-x+y+1/2`, omitting zero terms.
+pub(crate) fn format_expression(coefficients: &[f64; 3], constant: f64) -> String {
+    let mut res = String::new();
+    for (c, name) in coefficients.iter().zip(['x', 'y', 'z'].iter()) {
+        if fraction(*c) == Some((0, 1)) {
+            continue;
+        }
+        if *c < 0.0 {
+            res.push('-');
+        } else if !res.is_empty() {
+            res.push('+');
+        }
+        if fraction(c.abs()) != Some((1, 1)) {
+            res.push_str(&format_number(c.abs()));
+        }
+        res.push(*name);
+    }
+    if res.is_empty() {
+        res.push_str(&format_number(constant));
+    } else if fraction(constant) != Some((0, 1)) {
+        res.push(if constant < 0.0 { '-' } else { '+' });
+        res.push_str(&format_number(constant.abs()));
+    }
+    res
+}
+
+// Formats a vector as comma-separated numbers, e.g. `0,1/2,0`.
+pub(crate) fn format_vector(v: &[f64; 3]) -> String {
+    v.iter()
+        .map(|x| format_number(*x))
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+// Parses an affine expression such as `-x+1/2`, `1/2+y` or `x-2*y`, returning the coefficients
+// of x, y and z along with the constant, or a description of the problem.
+pub(crate) fn parse_expression(s: &str) -> Result<([f64; 3], f64), String> {
+    let chars: Vec<char> = s.chars().filter(|c| !c.is_whitespace()).collect();
+    if chars.is_empty() {
+        return Err("empty expression".to_string());
+    }
+    let mut coefficients = [0.0; 3];
+    let mut constant = 0.0;
+    let mut i = 0;
+    while i < chars.len() {
+        let mut sign = 1.0;
+        let mut has_sign = false;
+        while i < chars.len() && (chars[i] == '+' || chars[i] == '-') {
+            if chars[i] == '-' {
+                sign = -sign;
+            }
+            has_sign = true;
+            i += 1;
+        }
+        if i > 0 && !has_sign {
+            return Err(format!("expected '+' or '-' before {:?}", chars[i]));
+        }
+        let start = i;
+        while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.' || chars[i] == '/') {
+            i += 1;
+        }
+        let number = if i > start {
+            Some(parse_number(&chars[start..i].iter().collect::<String>())?)
+        } else {
+            None
+        };
+        if number.is_some() && i < chars.len() && chars[i] == '*' {
+            i += 1;
+        }
+        match chars.get(i).map(|c| c.to_ascii_lowercase()) {
+            Some(c @ 'x'..='z') => {
+                coefficients[c as usize - 'x' as usize] += sign * number.unwrap_or(1.0);
+                i += 1;
+            }
+            _ => match number {
+                Some(number) => constant += sign * number,
+                None => match chars.get(i) {
+                    Some(c) => return Err(format!("unexpected {:?}", c)),
+                    None => return Err("expression ends with a sign".to_string()),
+                },
+            },
+        }
+    }
+    Ok((coefficients, constant))
+}
+
+// Parses an integer, decimal or fraction such as `1/2`.
+fn parse_number(s: &str) -> Result<f64, String> {
+    let invalid = || format!("invalid number {:?}", s);
+    let mut parts = s.splitn(2, '/');
+    let numerator: f64 = parts.next().unwrap_or("").parse().map_err(|_| invalid())?;
+    match parts.next() {
+        Some(denominator) => {
+            let denominator: f64 = denominator.parse().map_err(|_| invalid())?;
+            if denominator == 0.0 {
+                return Err(invalid());
+            }
+            Ok(numerator / denominator)
+        }
+        None => Ok(numerator),
+    }
+}
+
+// Reduces a Hermann-Mauguin symbol to a compact spelling such as `P21/c` or `Fm-3m`, dropping
+// spaces, underscores and parentheses around subscripts, turning unicode subscripts, minus
+// signs and overbars into ASCII, and fixing the case of the lattice and glide letters.
+pub(crate) fn normalize_hermann_mauguin(s: &str) -> String {
+    let mut res = String::new();
+    for c in s.chars() {
+        match c {
+            '_' | '(' | ')' | '{' | '}' => {}
+            c if c.is_whitespace() => {}
+            '\u{2212}' | '\u{2013}' => res.push('-'),
+            // combining overbar or macron following the digit it applies to
+            '\u{0304}' | '\u{0305}' => {
+                if let Some(last) = res.pop() {
+                    res.push('-');
+                    res.push(last);
+                }
+            }
+            '\u{2080}'..='\u{2089}' => {
+                res.push(char::from(b'0' + (c as u32 - 0x2080) as u8));
+            }
+            c if res.is_empty() => res.push(c.to_ascii_uppercase()),
+            c => res.push(c.to_ascii_lowercase()),
+        }
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::notation::{
+        format_expression, format_number, normalize_hermann_mauguin, parse_expression,
+    };
+
+    #[test]
+    fn expressions() {
+        assert_eq!(format_number(0.125), "1/8");
+        assert_eq!(format_number(-2.0 / 3.0), "-2/3");
+        assert_eq!(format_number(-0.0), "0");
+        assert_eq!(format_expression(&[-1., 0., 0.], 0.5), "-x+1/2");
+        assert_eq!(format_expression(&[1., -1., 0.], 0.), "x-y");
+        assert_eq!(format_expression(&[0., 2., 0.], -0.25), "2y-1/4");
+        assert_eq!(format_expression(&[0., 0., 0.], 0.), "0");
+    }
+
+    #[test]
+    fn parse_expressions() {
+        assert_eq!(parse_expression("-x+1/2"), Ok(([-1., 0., 0.], 0.5)));
+        assert_eq!(parse_expression(" 1/2 + Y "), Ok(([0., 1., 0.], 0.5)));
+        assert_eq!(parse_expression("x-2*y-0.25"), Ok(([1., -2., 0.], -0.25)));
+        assert_eq!(parse_expression("--z"), Ok(([0., 0., 1.], 0.)));
+        assert!(parse_expression("").is_err());
+        assert!(parse_expression("x y").is_err());
+        assert!(parse_expression("x+").is_err());
+        assert!(parse_expression("x+1/0").is_err());
+        assert!(parse_expression("w").is_err());
+    }
+
+    #[test]
+    fn hermann_mauguin_spellings() {
+        assert_eq!(normalize_hermann_mauguin("P 1 2_1/c 1"), "P121/c1");
+        assert_eq!(normalize_hermann_mauguin("p2(1)/C"), "P21/c");
+        assert_eq!(normalize_hermann_mauguin("F m \u{2212}3 m"), "Fm-3m");
+        assert_eq!(normalize_hermann_mauguin("P4\u{0305}3m"), "P-43m");
+        assert_eq!(
+            normalize_hermann_mauguin("P2\u{2081}2\u{2081}2\u{2081}"),
+            "P212121"
+        );
+    }
+}
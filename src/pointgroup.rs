@@ -0,0 +1,283 @@
+//! Crystallographic point groups.
+
+use std::fmt;
+
+/// The 32 crystallographic point groups, named in Schoenflies notation.
+///
+/// The discriminants are the point group numbers used by spglib.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PointGroup {
+    /// Point group `1`.
+    C1 = 1,
+    /// Point group `-1`.
+    Ci,
+    /// Point group `2`.
+    C2,
+    /// Point group `m`.
+    Cs,
+    /// Point group `2/m`.
+    C2h,
+    /// Point group `222`.
+    D2,
+    /// Point group `mm2`.
+    C2v,
+    /// Point group `mmm`.
+    D2h,
+    /// Point group `4`.
+    C4,
+    /// Point group `-4`.
+    S4,
+    /// Point group `4/m`.
+    C4h,
+    /// Point group `422`.
+    D4,
+    /// Point group `4mm`.
+    C4v,
+    /// Point group `-42m`.
+    D2d,
+    /// Point group `4/mmm`.
+    D4h,
+    /// Point group `3`.
+    C3,
+    /// Point group `-3`.
+    C3i,
+    /// Point group `32`.
+    D3,
+    /// Point group `3m`.
+    C3v,
+    /// Point group `-3m`.
+    D3d,
+    /// Point group `6`.
+    C6,
+    /// Point group `-6`.
+    C3h,
+    /// Point group `6/m`.
+    C6h,
+    /// Point group `622`.
+    D6,
+    /// Point group `6mm`.
+    C6v,
+    /// Point group `-6m2`.
+    D3h,
+    /// Point group `6/mmm`.
+    D6h,
+    /// Point group `23`.
+    T,
+    /// Point group `m-3`.
+    Th,
+    /// Point group `432`.
+    O,
+    /// Point group `-43m`.
+    Td,
+    /// Point group `m-3m`.
+    Oh,
+}
+
+// Point groups in order of their number.
+const POINT_GROUPS: [PointGroup; 32] = [
+    PointGroup::C1,
+    PointGroup::Ci,
+    PointGroup::C2,
+    PointGroup::Cs,
+    PointGroup::C2h,
+    PointGroup::D2,
+    PointGroup::C2v,
+    PointGroup::D2h,
+    PointGroup::C4,
+    PointGroup::S4,
+    PointGroup::C4h,
+    PointGroup::D4,
+    PointGroup::C4v,
+    PointGroup::D2d,
+    PointGroup::D4h,
+    PointGroup::C3,
+    PointGroup::C3i,
+    PointGroup::D3,
+    PointGroup::C3v,
+    PointGroup::D3d,
+    PointGroup::C6,
+    PointGroup::C3h,
+    PointGroup::C6h,
+    PointGroup::D6,
+    PointGroup::C6v,
+    PointGroup::D3h,
+    PointGroup::D6h,
+    PointGroup::T,
+    PointGroup::Th,
+    PointGroup::O,
+    PointGroup::Td,
+    PointGroup::Oh,
+];
+
+impl PointGroup {
+    /// Returns the point group with the given number from 1-32.
+    pub fn from_number(number: i32) -> Option<PointGroup> {
+        if (1..=32).contains(&number) {
+            Some(POINT_GROUPS[number as usize - 1])
+        } else {
+            None
+        }
+    }
+
+    /// Returns the point group with the given Hermann-Mauguin symbol, e.g. `"4/mmm"`.
+    pub fn from_international(symbol: &str) -> Option<PointGroup> {
+        POINT_GROUPS
+            .iter()
+            .find(|pointgroup| pointgroup.international() == symbol.trim())
+            .copied()
+    }
+
+    /// Returns the point group number from 1-32.
+    pub fn number(self) -> i32 {
+        self as i32
+    }
+
+    /// Returns the Hermann-Mauguin symbol.
+    pub fn international(self) -> &'static str {
+        match self {
+            PointGroup::C1 => "1",
+            PointGroup::Ci => "-1",
+            PointGroup::C2 => "2",
+            PointGroup::Cs => "m",
+            PointGroup::C2h => "2/m",
+            PointGroup::D2 => "222",
+            PointGroup::C2v => "mm2",
+            PointGroup::D2h => "mmm",
+            PointGroup::C4 => "4",
+            PointGroup::S4 => "-4",
+            PointGroup::C4h => "4/m",
+            PointGroup::D4 => "422",
+            PointGroup::C4v => "4mm",
+            PointGroup::D2d => "-42m",
+            PointGroup::D4h => "4/mmm",
+            PointGroup::C3 => "3",
+            PointGroup::C3i => "-3",
+            PointGroup::D3 => "32",
+            PointGroup::C3v => "3m",
+            PointGroup::D3d => "-3m",
+            PointGroup::C6 => "6",
+            PointGroup::C3h => "-6",
+            PointGroup::C6h => "6/m",
+            PointGroup::D6 => "622",
+            PointGroup::C6v => "6mm",
+            PointGroup::D3h => "-6m2",
+            PointGroup::D6h => "6/mmm",
+            PointGroup::T => "23",
+            PointGroup::Th => "m-3",
+            PointGroup::O => "432",
+            PointGroup::Td => "-43m",
+            PointGroup::Oh => "m-3m",
+        }
+    }
+
+    /// Returns the Schoenflies symbol.
+    pub fn schoenflies(self) -> &'static str {
+        match self {
+            PointGroup::C1 => "C1",
+            PointGroup::Ci => "Ci",
+            PointGroup::C2 => "C2",
+            PointGroup::Cs => "Cs",
+            PointGroup::C2h => "C2h",
+            PointGroup::D2 => "D2",
+            PointGroup::C2v => "C2v",
+            PointGroup::D2h => "D2h",
+            PointGroup::C4 => "C4",
+            PointGroup::S4 => "S4",
+            PointGroup::C4h => "C4h",
+            PointGroup::D4 => "D4",
+            PointGroup::C4v => "C4v",
+            PointGroup::D2d => "D2d",
+            PointGroup::D4h => "D4h",
+            PointGroup::C3 => "C3",
+            PointGroup::C3i => "C3i",
+            PointGroup::D3 => "D3",
+            PointGroup::C3v => "C3v",
+            PointGroup::D3d => "D3d",
+            PointGroup::C6 => "C6",
+            PointGroup::C3h => "C3h",
+            PointGroup::C6h => "C6h",
+            PointGroup::D6 => "D6",
+            PointGroup::C6v => "C6v",
+            PointGroup::D3h => "D3h",
+            PointGroup::D6h => "D6h",
+            PointGroup::T => "T",
+            PointGroup::Th => "Th",
+            PointGroup::O => "O",
+            PointGroup::Td => "Td",
+            PointGroup::Oh => "Oh",
+        }
+    }
+}
+
+/// Formats the point group in Hermann-Mauguin notation, or in Schoenflies notation with `{:#}`.
+impl fmt::Display for PointGroup {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.alternate() {
+            write!(f, "{}", self.schoenflies())
+        } else {
+            write!(f, "{}", self.international())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::{InputError, SpglibError};
+    use crate::pointgroup::PointGroup;
+    use crate::pointgroup_from_rotations;
+
+    #[test]
+    fn pointgroup_numbers() {
+        for number in 1..=32 {
+            let pointgroup = PointGroup::from_number(number).unwrap();
+            assert_eq!(pointgroup.number(), number);
+            assert_eq!(
+                PointGroup::from_international(pointgroup.international()),
+                Some(pointgroup)
+            );
+        }
+        assert_eq!(PointGroup::from_number(0), None);
+        assert_eq!(PointGroup::from_number(33), None);
+        assert_eq!(PointGroup::D4h.to_string(), "4/mmm");
+        assert_eq!(PointGroup::C3i.schoenflies(), "C3i");
+    }
+
+    #[test]
+    fn pointgroup_from_hexagonal_rotations() {
+        // generators of 6/m along c in the hexagonal basis
+        let six = [[1, -1, 0], [1, 0, 0], [0, 0, 1]];
+        let inversion = [[-1, 0, 0], [0, -1, 0], [0, 0, -1]];
+        let mut rotations = vec![[[1, 0, 0], [0, 1, 0], [0, 0, 1]]];
+        let mut power = six;
+        while power != rotations[0] {
+            rotations.push(power);
+            power = multiply(&power, &six);
+        }
+        let proper = rotations.clone();
+        rotations.extend(proper.iter().map(|r| multiply(r, &inversion)));
+        let (pointgroup, _) = pointgroup_from_rotations(&rotations).unwrap();
+        assert_eq!(pointgroup, PointGroup::C6h);
+        let (pointgroup, _) = pointgroup_from_rotations(&proper[..1]).unwrap();
+        assert_eq!(pointgroup, PointGroup::C1);
+        let err = pointgroup_from_rotations(&[]).unwrap_err();
+        assert!(matches!(
+            err.kind,
+            SpglibError::InvalidInput(InputError::NoRotations)
+        ));
+        let err = pointgroup_from_rotations(&[[[2, 0, 0], [0, 1, 0], [0, 0, 1]]]).unwrap_err();
+        assert!(matches!(
+            err.kind,
+            SpglibError::InvalidInput(InputError::NotUnimodular { index: 0 })
+        ));
+    }
+
+    fn multiply(a: &[[i32; 3]; 3], b: &[[i32; 3]; 3]) -> [[i32; 3]; 3] {
+        let mut res = [[0; 3]; 3];
+        for i in 0..3 {
+            for j in 0..3 {
+                res[i][j] = (0..3).map(|k| a[i][k] * b[k][j]).sum();
+            }
+        }
+        res
+    }
+}
@@ -2,14 +2,21 @@
 
 use std::convert::TryFrom;
 use std::ffi::{CStr, CString};
+use std::fmt;
+use std::sync::OnceLock;
 
 use spglib_sys as ffi;
 
-use crate::error::SpglibError;
+use crate::crystal::{BravaisLattice, Centering, CrystalSystem, LatticeSystem};
+use crate::error::{Error, ParseError, SpglibError};
+use crate::notation::normalize_hermann_mauguin;
+use crate::pointgroup::PointGroup;
 
 /// Container for a spacegroup's properties
 #[derive(Clone, Debug)]
 pub struct Spacegroup {
+    /// Serial number of the hall symbol from 1-530.
+    pub hall_number: i32,
     /// Space group number as defined in the International Tables for Crystallography.
     pub number: i32,
     /// Abbreviated Hermann-Mauguin notation spacegroup symbol.
@@ -34,13 +41,134 @@ pub struct Spacegroup {
     pub arithmetic_crystal_class_symbol: String,
 }
 
-impl TryFrom<ffi::SpglibSpacegroupType> for Spacegroup {
+/// Space group symbol of a cell along with its space group number.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SpacegroupSymbol {
+    /// Space group number as defined in the International Tables for Crystallography.
+    pub number: i32,
+    /// Space group symbol in the requested notation.
+    pub symbol: String,
+}
+
+/// Symmetry operations of a space group setting, as tabulated in the spglib database.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SpacegroupOperations {
+    /// Rotation (matrix) parts of the symmetry operations.
+    pub rotations: Vec<[[i32; 3]; 3]>,
+    /// Translation (vector) parts of the symmetry operations.
+    pub translations: Vec<[f64; 3]>,
+}
+
+/// Space group type number, validated to lie in the range 1-230.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SpacegroupNumber(i32);
+
+impl SpacegroupNumber {
+    /// Returns the validated space group number.
+    ///
+    /// Fails if the number is outside of the range 1-230.
+    pub fn new(number: i32) -> Result<SpacegroupNumber, Error> {
+        if !(1..=230).contains(&number) {
+            return Err(Error::out_of_range(number, 230));
+        }
+        Ok(SpacegroupNumber(number))
+    }
+
+    /// Returns the number as an integer.
+    pub fn get(self) -> i32 {
+        self.0
+    }
+}
+
+impl TryFrom<i32> for SpacegroupNumber {
+    type Error = Error;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        SpacegroupNumber::new(value)
+    }
+}
+
+impl From<SpacegroupNumber> for i32 {
+    fn from(value: SpacegroupNumber) -> Self {
+        value.0
+    }
+}
+
+impl fmt::Display for SpacegroupNumber {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Serial number of a hall symbol, validated to lie in the range 1-530.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct HallNumber(i32);
+
+impl HallNumber {
+    /// Returns the validated hall number.
+    ///
+    /// Fails if the number is outside of the range 1-530.
+    pub fn new(number: i32) -> Result<HallNumber, Error> {
+        if !(1..=530).contains(&number) {
+            return Err(Error::out_of_range(number, 530));
+        }
+        Ok(HallNumber(number))
+    }
+
+    /// Returns the number as an integer.
+    pub fn get(self) -> i32 {
+        self.0
+    }
+}
+
+impl TryFrom<i32> for HallNumber {
+    type Error = Error;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        HallNumber::new(value)
+    }
+}
+
+impl From<HallNumber> for i32 {
+    fn from(value: HallNumber) -> Self {
+        value.0
+    }
+}
+
+impl fmt::Display for HallNumber {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Looks up the setting of a validated hall number, which cannot fail.
+impl From<HallNumber> for Spacegroup {
+    fn from(value: HallNumber) -> Self {
+        Spacegroup::from_hall_number(value.0).expect("hall numbers 1-530 are tabulated")
+    }
+}
+
+// Returns the 530 tabulated settings in order of hall number, read from spglib once.
+fn settings_table() -> &'static [Spacegroup] {
+    static SETTINGS: OnceLock<Vec<Spacegroup>> = OnceLock::new();
+    SETTINGS.get_or_init(|| {
+        (1..=530)
+            .map(|hall_number| Spacegroup::from(HallNumber(hall_number)))
+            .collect()
+    })
+}
+
+// Internal wrapper struct pairing the spacegroup type with the hall number it was queried by.
+struct SpglibSpacegroupType(i32, ffi::SpglibSpacegroupType);
+
+impl TryFrom<SpglibSpacegroupType> for Spacegroup {
     type Error = SpglibError;
 
-    fn try_from(value: ffi::SpglibSpacegroupType) -> Result<Self, Self::Error> {
-        let ptr = value;
+    fn try_from(value: SpglibSpacegroupType) -> Result<Self, Self::Error> {
+        let hall_number = value.0;
+        let ptr = value.1;
         // process fields
-        let number = ptr.number as i32;
+        let number = ptr.number;
         let international_short =
             match CString::from(unsafe { CStr::from_ptr(ptr.international_short.as_ptr()) })
                 .to_str()
@@ -89,7 +217,7 @@ impl TryFrom<ffi::SpglibSpacegroupType> for Spacegroup {
                 Ok(s) => String::from(s),
                 Err(_) => return Err(SpglibError::Unknown),
             };
-        let arithmetic_crystal_class_number = ptr.arithmetic_crystal_class_number as i32;
+        let arithmetic_crystal_class_number = ptr.arithmetic_crystal_class_number;
         let arithmetic_crystal_class_symbol = match CString::from(unsafe {
             CStr::from_ptr(ptr.arithmetic_crystal_class_symbol.as_ptr())
         })
@@ -99,6 +227,7 @@ impl TryFrom<ffi::SpglibSpacegroupType> for Spacegroup {
             Err(_) => return Err(SpglibError::Unknown),
         };
         Ok(Spacegroup {
+            hall_number,
             number,
             international_short,
             international_full,
@@ -117,6 +246,8 @@ impl TryFrom<ffi::SpglibSpacegroupType> for Spacegroup {
 impl Spacegroup {
     /// Returns a spacegroup initialized by hall number
     ///
+    /// Fails if the hall number is outside of the range 1-530.
+    ///
     /// # Example
     ///
     /// Get the spacegroup for a BCC cell (hall number 529).
@@ -124,21 +255,346 @@ impl Spacegroup {
     /// ```
     /// use spglib::spacegroup::Spacegroup;
     ///
-    /// let group = Spacegroup::from_hall_number(529);
+    /// let group = Spacegroup::from_hall_number(529).unwrap();
     /// assert_eq!("Im-3m", group.international_short);
     /// ```
-    pub fn from_hall_number(hall_number: i32) -> Spacegroup {
+    pub fn from_hall_number(hall_number: i32) -> Result<Spacegroup, Error> {
+        // checked here, as spglib's error code is shared with calls on other threads
+        HallNumber::new(hall_number)?;
         let raw = unsafe { ffi::spg_get_spacegroup_type(hall_number) };
-        Spacegroup::try_from(raw).unwrap()
+        if raw.number == 0 {
+            return Err(Error::last("spg_get_spacegroup_type"));
+        }
+        Spacegroup::try_from(SpglibSpacegroupType(hall_number, raw))
+            .map_err(|kind| Error::new(kind, "spg_get_spacegroup_type"))
+    }
+
+    /// Returns an iterator over the 530 tabulated settings, in order of hall number.
+    ///
+    /// The settings are read from spglib on the first call and cached.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spglib::spacegroup::Spacegroup;
+    ///
+    /// let monoclinic = Spacegroup::all_settings()
+    ///     .filter(|group| (3..=15).contains(&group.number))
+    ///     .count();
+    /// assert_eq!(monoclinic, 105);
+    /// ```
+    pub fn all_settings() -> impl Iterator<Item = Spacegroup> {
+        settings_table().iter().cloned()
+    }
+
+    /// Returns an iterator over the 230 space group types in their default settings,
+    /// in order of space group number.
+    ///
+    /// The default setting of a type is the one with the smallest hall number, as used by spglib.
+    pub fn all_types() -> impl Iterator<Item = Spacegroup> {
+        let mut last = 0;
+        Spacegroup::all_settings().filter(move |group| {
+            let first = group.number != last;
+            last = group.number;
+            first
+        })
+    }
+
+    /// Returns the default setting of a space group type.
+    ///
+    /// Fails if the number is outside of the range 1-230.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spglib::spacegroup::Spacegroup;
+    ///
+    /// let group = Spacegroup::from_number(14).unwrap();
+    /// assert_eq!(group.hall_number, 81);
+    /// assert_eq!(group.hall_symbol, "-P 2ybc");
+    /// ```
+    pub fn from_number(number: i32) -> Result<Spacegroup, Error> {
+        // every type has at least one setting
+        Spacegroup::settings(number).map(|mut settings| settings.remove(0))
+    }
+
+    /// Returns all settings of a space group type, in order of hall number.
+    ///
+    /// Fails if the number is outside of the range 1-230.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spglib::spacegroup::Spacegroup;
+    ///
+    /// let settings = Spacegroup::settings(14).unwrap();
+    /// assert_eq!(settings.len(), 9);
+    /// assert_eq!(settings[3].choice, "c1");
+    /// ```
+    pub fn settings(number: i32) -> Result<Vec<Spacegroup>, Error> {
+        SpacegroupNumber::new(number)?;
+        Ok(settings_table()
+            .iter()
+            .skip_while(|group| group.number != number)
+            .take_while(|group| group.number == number)
+            .cloned()
+            .collect())
+    }
+
+    /// Returns the first setting with the given short Hermann-Mauguin symbol, e.g. `"Fm-3m"`.
+    ///
+    /// Settings that differ only in the unique axis or origin share a short symbol, in which case
+    /// the default one is returned.
+    pub fn from_international_short(symbol: &str) -> Result<Spacegroup, ParseError> {
+        Spacegroup::find(
+            |group| group.international_short == symbol,
+            "short symbol",
+            symbol,
+        )
+    }
+
+    /// Returns the first setting with the given full Hermann-Mauguin symbol, e.g. `"P 1 2_1/c 1"`.
+    ///
+    /// Origin choices share a full symbol, in which case the first choice is returned.
+    pub fn from_international_full(symbol: &str) -> Result<Spacegroup, ParseError> {
+        Spacegroup::find(
+            |group| group.international_full == symbol,
+            "full symbol",
+            symbol,
+        )
+    }
+
+    /// Returns the first setting with the given hall symbol, e.g. `"-P 2ybc"`.
+    ///
+    /// The symbols are unique except for three pairs of equivalent settings of space group type 68
+    /// (hall numbers 322 and 324, 326 and 328, 330 and 332).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spglib::spacegroup::Spacegroup;
+    ///
+    /// let group = Spacegroup::from_hall_symbol("-F 4 2 3").unwrap();
+    /// assert_eq!(group.hall_number, 523);
+    /// assert!(Spacegroup::from_hall_symbol("-F 4 2 4").is_err());
+    /// ```
+    pub fn from_hall_symbol(symbol: &str) -> Result<Spacegroup, ParseError> {
+        Spacegroup::find(|group| group.hall_symbol == symbol, "hall symbol", symbol)
+    }
+
+    /// Returns the settings matching a Hermann-Mauguin symbol in any common spelling,
+    /// in order of hall number.
+    ///
+    /// Spaces, subscripts written as `2_1`, `2(1)` or `2₁` and overbars written as `-3`, `−3` or
+    /// `3̄` are all accepted, and cubic symbols may omit the overbar as in `Fm3m`. Short symbols of
+    /// monoclinic groups match every setting whose full symbol reduces to them, e.g. `P2_1/c` matches
+    /// unique axes b and a. A suffix such as `:H`, `:R`, `:2` or `:b1` picks the setting of the
+    /// matched type with that choice of axes or origin; without one, all choices are returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spglib::spacegroup::Spacegroup;
+    ///
+    /// let settings = Spacegroup::settings_from_symbol("R-3c").unwrap();
+    /// assert_eq!(settings.len(), 2);
+    /// let group = &Spacegroup::settings_from_symbol("R -3 c:R").unwrap()[0];
+    /// assert_eq!(group.hall_number, 461);
+    /// let group = &Spacegroup::settings_from_symbol("P 1 21/n 1").unwrap()[0];
+    /// assert_eq!(group.choice, "b2");
+    /// ```
+    pub fn settings_from_symbol(symbol: &str) -> Result<Vec<Spacegroup>, ParseError> {
+        let (body, choice) = match symbol.rfind(':') {
+            Some(i) => (&symbol[..i], Some(symbol[i + 1..].trim())),
+            None => (symbol, None),
+        };
+        let key = normalize_hermann_mauguin(body);
+        let settings = settings_table();
+        let select = |matches: &dyn Fn(&Spacegroup) -> bool| -> Vec<&Spacegroup> {
+            settings.iter().filter(|group| matches(group)).collect()
+        };
+        let mut matches = select(&|group| {
+            group
+                .full_symbols()
+                .iter()
+                .any(|s| normalize_hermann_mauguin(s) == key)
+        });
+        if matches.is_empty() {
+            matches = select(&|group| normalize_hermann_mauguin(&group.international_short) == key);
+        }
+        if matches.is_empty() && !key.contains('-') {
+            matches = select(&|group| {
+                group.number >= 195
+                    && normalize_hermann_mauguin(&group.international_short).replace('-', "") == key
+            });
+        }
+        if let Some(choice) = choice {
+            let numbers: Vec<i32> = matches.iter().map(|group| group.number).collect();
+            matches = select(&|group| {
+                numbers.contains(&group.number) && group.choice.eq_ignore_ascii_case(choice)
+            });
+        }
+        if matches.is_empty() {
+            return Err(ParseError::new(
+                symbol,
+                "no setting has this Hermann-Mauguin symbol",
+            ));
+        }
+        Ok(matches.into_iter().cloned().collect())
+    }
+
+    /// Returns the first setting matching a Hermann-Mauguin symbol in any common spelling.
+    ///
+    /// See [`settings_from_symbol`](Spacegroup::settings_from_symbol) for the accepted spellings.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spglib::spacegroup::Spacegroup;
+    ///
+    /// assert_eq!(Spacegroup::from_symbol("P2_1/c").unwrap().hall_number, 81);
+    /// assert_eq!(Spacegroup::from_symbol("fm3m").unwrap().hall_number, 523);
+    /// assert_eq!(Spacegroup::from_symbol("Pn-3m:2").unwrap().hall_number, 522);
+    /// ```
+    pub fn from_symbol(symbol: &str) -> Result<Spacegroup, ParseError> {
+        Spacegroup::settings_from_symbol(symbol).map(|mut settings| settings.remove(0))
+    }
+
+    // Returns the spellings that identify this setting among the settings of its type: the full
+    // symbol, the alternative symbols in `international`, and for monoclinic groups the full
+    // symbol without the 1s of the other axes.
+    fn full_symbols(&self) -> Vec<String> {
+        let mut symbols = vec![self.international_full.clone()];
+        let mut alternatives = self.international.split('=').map(str::trim);
+        if self.international.contains('=') {
+            alternatives.next();
+        }
+        symbols.extend(alternatives.map(String::from));
+        if (3..=15).contains(&self.number) {
+            let reduced: Vec<&str> = self
+                .international_full
+                .split_whitespace()
+                .filter(|s| *s != "1")
+                .collect();
+            symbols.push(reduced.join(" "));
+        }
+        symbols
+    }
+
+    // Returns the first setting matching a predicate.
+    fn find<P>(mut predicate: P, what: &str, symbol: &str) -> Result<Spacegroup, ParseError>
+    where
+        P: FnMut(&Spacegroup) -> bool,
+    {
+        settings_table()
+            .iter()
+            .find(|group| predicate(group))
+            .cloned()
+            .ok_or_else(|| ParseError::new(symbol, format!("no setting has this {}", what)))
+    }
+
+    /// Returns the validated space group number.
+    pub fn spacegroup_number(&self) -> SpacegroupNumber {
+        SpacegroupNumber(self.number)
+    }
+
+    /// Returns the crystal system of the space group type.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spglib::spacegroup::Spacegroup;
+    ///
+    /// let group = Spacegroup::from_hall_number(523).unwrap();
+    /// assert_eq!(group.crystal_system().to_string(), "cubic");
+    /// assert_eq!(group.bravais_lattice().to_string(), "cF");
+    /// assert_eq!(format!("{} {:#}", group.point_group(), group.point_group()), "m-3m Oh");
+    /// ```
+    pub fn crystal_system(&self) -> CrystalSystem {
+        CrystalSystem::from_spacegroup_number(self.spacegroup_number())
+    }
+
+    /// Returns the lattice system of the space group type.
+    pub fn lattice_system(&self) -> LatticeSystem {
+        LatticeSystem::new(self.spacegroup_number(), self.centering())
+    }
+
+    /// Returns the centering of the conventional cell in this setting.
+    pub fn centering(&self) -> Centering {
+        Centering::from_international(&self.international_short)
+            .expect("symbols from spglib start with a lattice letter")
+    }
+
+    /// Returns the Bravais lattice of the space group type.
+    pub fn bravais_lattice(&self) -> BravaisLattice {
+        BravaisLattice::new(self.spacegroup_number(), self.centering())
+            .expect("settings from spglib have a valid centering")
+    }
+
+    /// Returns the point group of the space group type.
+    pub fn point_group(&self) -> PointGroup {
+        PointGroup::from_international(&self.pointgroup_international)
+            .expect("point group symbols from spglib are known")
+    }
+
+    /// Returns the rotations and translations of the space group operations in this setting.
+    /// Refer to the full documentation of the implementation [here](https://spglib.github.io/spglib/api.html#spg-get-symmetry-from-database).
+    ///
+    /// The operations include the centering translations of the conventional cell.
+    ///
+    /// # Example
+    ///
+    /// Generate the conventional FCC cell from a single atom.
+    ///
+    /// ```
+    /// use spglib::spacegroup::Spacegroup;
+    ///
+    /// let group = Spacegroup::from_hall_number(523).unwrap();
+    /// assert_eq!(group.international_short, "Fm-3m");
+    /// let operations = group.operations().unwrap();
+    /// assert_eq!(operations.rotations.len(), 192);
+    /// // the images of the origin are the translations themselves
+    /// let mut positions: Vec<[f64; 3]> = Vec::new();
+    /// for translation in operations.translations.iter() {
+    ///     let position = translation.map(|x| x.rem_euclid(1.0));
+    ///     if !positions.contains(&position) {
+    ///         positions.push(position);
+    ///     }
+    /// }
+    /// assert_eq!(positions.len(), 4);
+    /// ```
+    pub fn operations(&self) -> Result<SpacegroupOperations, Error> {
+        let mut rotations = vec![[[0; 3]; 3]; 192];
+        let mut translations = vec![[0.0; 3]; 192];
+        let res = unsafe {
+            ffi::spg_get_symmetry_from_database(
+                rotations.as_mut_ptr(),
+                translations.as_mut_ptr(),
+                self.hall_number,
+            )
+        };
+        if res == 0 {
+            return Err(Error::last("spg_get_symmetry_from_database"));
+        }
+        rotations.truncate(res as usize);
+        translations.truncate(res as usize);
+        Ok(SpacegroupOperations {
+            rotations,
+            translations,
+        })
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::cell::Cell;
+    use crate::dataset::Dataset;
+    use crate::error::SpglibError;
     use crate::spacegroup::Spacegroup;
     #[test]
     fn spacegroup_from_hall_number() {
-        let res = Spacegroup::from_hall_number(446);
+        let res = Spacegroup::from_hall_number(446).unwrap();
+        assert_eq!(res.hall_number, 446);
         assert_eq!(res.number, 156);
         assert_eq!(&res.international_short, "P3m1");
         assert_eq!(&res.international_full, "P 3 m 1");
@@ -151,4 +607,154 @@ mod tests {
         assert_eq!(res.arithmetic_crystal_class_number, 45);
         assert_eq!(&res.arithmetic_crystal_class_symbol, "3m1P");
     }
+
+    #[test]
+    fn spacegroup_from_invalid_hall_number() {
+        for hall_number in [0, 531] {
+            let err = Spacegroup::from_hall_number(hall_number).unwrap_err();
+            assert!(matches!(
+                err.kind,
+                SpglibError::NumberOutOfRange { value, max: 530 } if value == hall_number
+            ));
+            assert_eq!(err.function, "");
+            assert_eq!(
+                err.to_string(),
+                format!("number {} is outside of the range 1-530", hall_number)
+            );
+        }
+    }
+
+    #[test]
+    fn spacegroup_operations() {
+        for hall_number in 1..=530 {
+            let group = Spacegroup::from_hall_number(hall_number).unwrap();
+            let operations = group.operations().unwrap();
+            assert_eq!(operations.rotations.len(), operations.translations.len());
+            assert_eq!(operations.rotations[0], [[1, 0, 0], [0, 1, 0], [0, 0, 1]]);
+        }
+    }
+
+    #[test]
+    fn spacegroup_operations_match_dataset() {
+        let lattice = [[4., 0., 0.], [0., 4., 0.], [0., 0., 4.]];
+        let positions = [[0., 0., 0.], [0.5, 0.5, 0.5]];
+        let cell = Cell::new(&lattice, &positions, &[1, 1]);
+        let dataset = Dataset::new(&cell, 1e-5).unwrap();
+        let group = Spacegroup::from_hall_number(dataset.hall_number).unwrap();
+        let operations = group.operations().unwrap();
+        let (rotations, translations) = (&operations.rotations, &operations.translations);
+        assert_eq!(rotations.len(), dataset.rotations.len());
+        for (rotation, translation) in dataset.rotations.iter().zip(dataset.translations.iter()) {
+            assert!(rotations
+                .iter()
+                .zip(translations.iter())
+                .any(|(r, t)| r == rotation
+                    && t.iter()
+                        .zip(translation.iter())
+                        .all(|(a, b)| ((a - b) - (a - b).round()).abs() < 1e-8)));
+        }
+    }
+
+    #[test]
+    fn spacegroup_enumeration() {
+        let settings: Vec<Spacegroup> = Spacegroup::all_settings().collect();
+        assert_eq!(settings.len(), 530);
+        let types: Vec<Spacegroup> = Spacegroup::all_types().collect();
+        assert_eq!(types.len(), 230);
+        for (i, group) in types.iter().enumerate() {
+            assert_eq!(group.number, i as i32 + 1);
+            let default = Spacegroup::from_number(group.number).unwrap();
+            assert_eq!(default.hall_number, group.hall_number);
+        }
+        let n_settings: usize = (1..=230)
+            .map(|number| Spacegroup::settings(number).unwrap().len())
+            .sum();
+        assert_eq!(n_settings, 530);
+        for number in [0, 231] {
+            let err = Spacegroup::from_number(number).unwrap_err();
+            assert!(matches!(
+                err.kind,
+                SpglibError::NumberOutOfRange { value, max: 230 } if value == number
+            ));
+        }
+    }
+
+    #[test]
+    fn spacegroup_lookup_by_symbol() {
+        for group in Spacegroup::all_settings() {
+            let found = Spacegroup::from_hall_symbol(&group.hall_symbol).unwrap();
+            assert_eq!(found.hall_symbol, group.hall_symbol);
+            if ![324, 328, 332].contains(&group.hall_number) {
+                assert_eq!(found.hall_number, group.hall_number);
+            }
+            let found = Spacegroup::from_international_short(&group.international_short).unwrap();
+            assert_eq!(found.number, group.number);
+            let found = Spacegroup::from_international_full(&group.international_full).unwrap();
+            assert_eq!(found.number, group.number);
+            assert!(found.hall_number <= group.hall_number);
+        }
+        let group = Spacegroup::from_international_full("P 1 1 2_1/a").unwrap();
+        assert_eq!(group.hall_number, 84);
+        let err = Spacegroup::from_international_short("P7").unwrap_err();
+        assert_eq!(err.input, "P7");
+        assert!(err.to_string().contains("\"P7\""));
+    }
+
+    #[test]
+    fn spacegroup_lookup_by_tolerant_symbol() {
+        let hall_numbers = |symbol: &str| -> Vec<i32> {
+            Spacegroup::settings_from_symbol(symbol)
+                .unwrap()
+                .iter()
+                .map(|group| group.hall_number)
+                .collect()
+        };
+        for group in Spacegroup::all_settings() {
+            // short symbols never match settings of another type
+            let found = Spacegroup::settings_from_symbol(&group.international_short).unwrap();
+            assert!(found.iter().all(|other| other.number == group.number));
+            let found = hall_numbers(&group.international_full);
+            assert!(found.contains(&group.hall_number));
+            if !group.choice.is_empty() {
+                let symbol = format!("{}:{}", group.international_short, group.choice);
+                assert_eq!(hall_numbers(&symbol), vec![group.hall_number]);
+            }
+        }
+        assert_eq!(hall_numbers("P2_1/c"), vec![81, 89]);
+        assert_eq!(hall_numbers("P 1 21/n 1"), vec![82]);
+        assert_eq!(hall_numbers("P21/n"), vec![82, 85, 88]);
+        assert_eq!(hall_numbers("P2\u{2081}/c:b1"), vec![81]);
+        assert_eq!(hall_numbers("Fm-3m"), vec![523]);
+        assert_eq!(hall_numbers("F m \u{2212}3 m"), vec![523]);
+        assert_eq!(hall_numbers("Fm3m"), vec![523]);
+        assert_eq!(hall_numbers("P4\u{0305}3m"), vec![511]);
+        assert_eq!(hall_numbers("R-3c"), vec![460, 461]);
+        assert_eq!(hall_numbers("R-3c:H"), vec![460]);
+        assert_eq!(hall_numbers("r-3c : r"), vec![461]);
+        assert_eq!(hall_numbers("pnma"), vec![292]);
+        assert_eq!(hall_numbers("Pnma:cab"), vec![294]);
+        assert_eq!(hall_numbers("Pbnm"), vec![294]);
+        assert_eq!(hall_numbers("Pn-3m"), vec![521, 522]);
+        assert_eq!(hall_numbers("Pn-3m:2"), vec![522]);
+        assert_eq!(hall_numbers("P3_2"), vec![432]);
+        assert_eq!(hall_numbers("P312"), vec![438]);
+        for symbol in ["", "P7", "Fm-3m:2", "P-4m3"] {
+            let err = Spacegroup::settings_from_symbol(symbol).unwrap_err();
+            assert_eq!(err.input, symbol);
+        }
+    }
+
+    #[test]
+    fn validated_numbers() {
+        use crate::spacegroup::{HallNumber, SpacegroupNumber};
+        use std::convert::TryFrom;
+
+        assert_eq!(SpacegroupNumber::new(230).unwrap().get(), 230);
+        assert!(SpacegroupNumber::new(0).is_err());
+        assert!(SpacegroupNumber::try_from(231).is_err());
+        let hall = HallNumber::try_from(530).unwrap();
+        assert_eq!(i32::from(hall), 530);
+        assert_eq!(Spacegroup::from(hall).number, 230);
+        assert!(HallNumber::new(531).is_err());
+    }
 }
@@ -0,0 +1,348 @@
+//! Symmetry operations of a structure.
+
+use std::fmt;
+use std::ops::Mul;
+use std::str::FromStr;
+
+use crate::element::SymmetryElement;
+use crate::error::{InputError, ParseError};
+use crate::math::{det_i, inverse, inverse_i, mat_mul_i, mat_vec, mat_vec_i, transpose_i};
+use crate::notation::{format_expression, parse_expression};
+
+// Largest difference of translations, modulo lattice translations, for operations to compare equal.
+const TRANSLATION_TOLERANCE: f64 = 1e-6;
+
+// Checks that rotations can be passed to spglib: there is at least one and each has
+// determinant 1 or -1.
+pub(crate) fn check_rotations(rotations: &[[[i32; 3]; 3]]) -> Result<(), InputError> {
+    if rotations.is_empty() {
+        return Err(InputError::NoRotations);
+    }
+    match rotations.iter().position(|r| det_i(r).abs() != 1) {
+        Some(index) => Err(InputError::NotUnimodular { index }),
+        None => Ok(()),
+    }
+}
+
+/// Affine symmetry operation `x -> Rx + t` acting on fractional coordinates.
+///
+/// Operations compare equal if their rotations agree and their translations agree up to a
+/// lattice translation, with an absolute tolerance of `1e-6` per component.
+///
+/// # Example
+///
+/// ```
+/// use spglib::symmetry::SymmetryOperation;
+///
+/// // 4_2 screw axis along c
+/// let screw = SymmetryOperation::new([[0, -1, 0], [1, 0, 0], [0, 0, 1]], [0., 0., 0.5]);
+/// assert_eq!(screw.order(), 4);
+/// assert_eq!(screw.pow(4), SymmetryOperation::identity());
+/// assert_eq!(screw * screw.inverse(), SymmetryOperation::identity());
+/// assert_eq!(screw.apply_point(&[0.5, 0., 0.]), [0., 0.5, 0.5]);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct SymmetryOperation {
+    /// The rotation (matrix) part.
+    pub rotation: [[i32; 3]; 3],
+    /// The translation (vector) part.
+    pub translation: [f64; 3],
+}
+
+impl SymmetryOperation {
+    /// Returns the operation with the given rotation and translation.
+    pub fn new(rotation: [[i32; 3]; 3], translation: [f64; 3]) -> SymmetryOperation {
+        SymmetryOperation {
+            rotation,
+            translation,
+        }
+    }
+
+    /// Returns the identity operation.
+    pub fn identity() -> SymmetryOperation {
+        SymmetryOperation::new([[1, 0, 0], [0, 1, 0], [0, 0, 1]], [0.0; 3])
+    }
+
+    /// Returns the inverse operation `x -> R^-1 x - R^-1 t`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rotation is not invertible over the integers, i.e. its determinant is not 1 or -1.
+    pub fn inverse(&self) -> SymmetryOperation {
+        let rotation = inverse_i(&self.rotation).expect("rotation must have determinant 1 or -1");
+        let translation = mat_vec_i(&rotation, &self.translation).map(|x| -x);
+        SymmetryOperation::new(rotation, translation)
+    }
+
+    /// Returns the operation applied `n` times, where negative `n` applies the inverse.
+    pub fn pow(&self, n: i32) -> SymmetryOperation {
+        let base = if n < 0 { self.inverse() } else { *self };
+        (0..n.abs()).fold(SymmetryOperation::identity(), |acc, _| acc * base)
+    }
+
+    /// Returns the order of the rotation part, one of 1, 2, 3, 4 or 6.
+    ///
+    /// Raising the operation to this power leaves a pure translation, which is a lattice
+    /// translation unless it involves a centering vector.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rotation is not crystallographic.
+    pub fn order(&self) -> u32 {
+        let identity = SymmetryOperation::identity().rotation;
+        let mut rotation = self.rotation;
+        for order in 1..=6 {
+            if rotation == identity {
+                return order;
+            }
+            rotation = mat_mul_i(&self.rotation, &rotation);
+        }
+        panic!("rotation {:?} is not crystallographic", self.rotation)
+    }
+
+    /// Returns the geometric description of the operation.
+    /// Refer to [`SymmetryElement`] for an example.
+    pub fn element(&self) -> SymmetryElement {
+        SymmetryElement::new(self)
+    }
+
+    /// Returns the image `Rx + t` of a point in fractional coordinates.
+    pub fn apply_point(&self, point: &[f64; 3]) -> [f64; 3] {
+        let mut res = mat_vec_i(&self.rotation, point);
+        for (x, t) in res.iter_mut().zip(self.translation.iter()) {
+            *x += t;
+        }
+        res
+    }
+
+    /// Returns the image of a Cartesian vector, which is only rotated.
+    ///
+    /// The columns of `lattice` are the basis vectors, as in `Cell::lattice`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the lattice is singular.
+    pub fn apply_cartesian_vector(&self, lattice: &[[f64; 3]; 3], vector: &[f64; 3]) -> [f64; 3] {
+        let fractional = mat_vec(
+            &inverse(lattice).expect("lattice must not be singular"),
+            vector,
+        );
+        mat_vec(lattice, &mat_vec_i(&self.rotation, &fractional))
+    }
+
+    /// Returns the image `R^-T k` of a reciprocal vector in coordinates of the reciprocal basis.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rotation is not invertible over the integers.
+    pub fn apply_reciprocal_vector(&self, vector: &[f64; 3]) -> [f64; 3] {
+        let rotation = inverse_i(&self.rotation).expect("rotation must have determinant 1 or -1");
+        mat_vec_i(&transpose_i(&rotation), vector)
+    }
+}
+
+/// Composes two operations, so that `(a * b).apply_point(x) == a.apply_point(&b.apply_point(x))`.
+impl Mul for SymmetryOperation {
+    type Output = SymmetryOperation;
+
+    fn mul(self, rhs: SymmetryOperation) -> SymmetryOperation {
+        let rotation = mat_mul_i(&self.rotation, &rhs.rotation);
+        SymmetryOperation::new(rotation, self.apply_point(&rhs.translation))
+    }
+}
+
+/// Formats the operation in Jones faithful notation with exact fractions, e.g. `-x+1/2,y,-z`.
+///
+/// # Example
+///
+/// ```
+/// use spglib::symmetry::SymmetryOperation;
+///
+/// let glide = SymmetryOperation::new([[-1, 0, 0], [0, 1, 0], [0, 0, 1]], [0.5, 0., 0.25]);
+/// assert_eq!(glide.to_string(), "-x+1/2,y,z+1/4");
+/// ```
+impl fmt::Display for SymmetryOperation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let rows: Vec<String> = self
+            .rotation
+            .iter()
+            .zip(self.translation.iter())
+            .map(|(row, t)| format_expression(&row.map(|x| x as f64), *t))
+            .collect();
+        write!(f, "{}", rows.join(","))
+    }
+}
+
+/// Parses an operation in Jones faithful notation, as used by CIF files and the
+/// International Tables, e.g. `-x+1/2,y,-z` or `'1/2+X, -Y, Z'`.
+///
+/// # Example
+///
+/// ```
+/// use spglib::symmetry::SymmetryOperation;
+///
+/// let operation: SymmetryOperation = "-y+1/2, x-y, z+1/3".parse().unwrap();
+/// assert_eq!(operation.rotation, [[0, -1, 0], [1, -1, 0], [0, 0, 1]]);
+/// assert_eq!(operation.translation, [0.5, 0., 1. / 3.]);
+/// ```
+impl FromStr for SymmetryOperation {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let trimmed = s.trim();
+        let unquoted = trimmed
+            .strip_prefix('\'')
+            .and_then(|t| t.strip_suffix('\''))
+            .or_else(|| trimmed.strip_prefix('"').and_then(|t| t.strip_suffix('"')))
+            .unwrap_or(trimmed);
+        let components: Vec<&str> = unquoted.split(',').collect();
+        if components.len() != 3 {
+            return Err(ParseError::new(
+                s,
+                "expected three comma-separated components",
+            ));
+        }
+        let mut rotation = [[0; 3]; 3];
+        let mut translation = [0.0; 3];
+        for (i, component) in components.iter().enumerate() {
+            let (coefficients, constant) =
+                parse_expression(component).map_err(|message| ParseError::new(s, message))?;
+            for (r, c) in rotation[i].iter_mut().zip(coefficients.iter()) {
+                if c.fract() != 0.0 {
+                    return Err(ParseError::new(s, "coefficients must be integers"));
+                }
+                *r = *c as i32;
+            }
+            translation[i] = constant;
+        }
+        let det = det_i(&rotation);
+        if det.abs() != 1 {
+            return Err(ParseError::new(
+                s,
+                format!("rotation has determinant {} instead of 1 or -1", det),
+            ));
+        }
+        Ok(SymmetryOperation::new(rotation, translation))
+    }
+}
+
+impl PartialEq for SymmetryOperation {
+    fn eq(&self, other: &SymmetryOperation) -> bool {
+        self.rotation == other.rotation
+            && self
+                .translation
+                .iter()
+                .zip(other.translation.iter())
+                .all(|(a, b)| {
+                    let d = a - b;
+                    (d - d.round()).abs() < TRANSLATION_TOLERANCE
+                })
+    }
+}
+
+/// Container for the space group operations of a cell.
+#[derive(Clone, Debug)]
+pub struct Symmetry {
+    /// The rotation symmetry operations.
+    pub rotations: Vec<[[i32; 3]; 3]>,
+    /// The translation symmetry operations.
+    pub translations: Vec<[f64; 3]>,
+    /// The atom permutation of each operation.
+    /// Operation `k` sends atom `i` onto atom `permutations[k][i]`.
+    pub permutations: Vec<Vec<usize>>,
+}
+
+/// Container for the magnetic symmetry of a cell with collinear spins.
+#[derive(Clone, Debug)]
+pub struct SpinSymmetry {
+    /// The operations which preserve the spin configuration, possibly combined with time reversal.
+    pub symmetry: Symmetry,
+    /// Whether each operation preserves the spins (1) or flips all of them (-1).
+    /// Operations which flip the spins only preserve the configuration when combined with time reversal.
+    pub spin_flips: Vec<i32>,
+    /// The mapping table from the atomic indices of the input cell to the atomic indices of
+    /// symmetrically independent atoms, taking the spins into account.
+    pub equivalent_atoms: Vec<i32>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cell::Cell;
+    use crate::dataset::Dataset;
+    use crate::spacegroup::Spacegroup;
+    use crate::symmetry::SymmetryOperation;
+
+    fn hexagonal_lattice() -> [[f64; 3]; 3] {
+        [[3., -1.5, 0.], [0., 1.5 * 3f64.sqrt(), 0.], [0., 0., 5.]]
+    }
+
+    #[test]
+    fn operation_algebra() {
+        let lattice = [[4., 0., 0.], [0., 4., 0.], [0., 0., 4.]];
+        let positions = [[0., 0., 0.], [0.5, 0.5, 0.5]];
+        let cell = Cell::new(&lattice, &positions, &[1, 1]);
+        let dataset = Dataset::new(&cell, 1e-5).unwrap();
+        let operations: Vec<SymmetryOperation> = dataset.operations().collect();
+        assert_eq!(operations.len(), 96);
+        for a in operations.iter() {
+            assert!([1, 2, 3, 4, 6].contains(&a.order()));
+            assert_eq!(*a * a.inverse(), SymmetryOperation::identity());
+            assert_eq!(a.pow(-2), a.inverse() * a.inverse());
+            for b in operations.iter() {
+                // the group is closed under composition
+                assert!(operations.contains(&(*a * *b)));
+            }
+        }
+        // the centering translation equals the identity only up to the body-centering vector
+        let centering = SymmetryOperation::new(SymmetryOperation::identity().rotation, [0.5; 3]);
+        assert_ne!(centering, SymmetryOperation::identity());
+        assert_eq!(centering.pow(2), SymmetryOperation::identity());
+    }
+
+    #[test]
+    fn operation_on_vectors() {
+        let lattice = hexagonal_lattice();
+        // six-fold rotation about c
+        let six = SymmetryOperation::new([[1, -1, 0], [1, 0, 0], [0, 0, 1]], [0.; 3]);
+        assert_eq!(six.order(), 6);
+        let v = six.apply_cartesian_vector(&lattice, &[1., 0., 2.]);
+        let expected = [0.5, 0.5 * 3f64.sqrt(), 2.];
+        for (a, b) in v.iter().zip(expected.iter()) {
+            assert!((a - b).abs() < 1e-12);
+        }
+        // reciprocal vectors keep their product with the images of points
+        let k = [1., 2., -1.];
+        let x = [0.1, 0.3, 0.7];
+        let rotated_k = six.apply_reciprocal_vector(&k);
+        let rotated_x = six.apply_point(&x);
+        let dot = |a: &[f64; 3], b: &[f64; 3]| a[0] * b[0] + a[1] * b[1] + a[2] * b[2];
+        assert!((dot(&rotated_k, &rotated_x) - dot(&k, &x)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn jones_notation_round_trip() {
+        for group in Spacegroup::all_settings() {
+            let operations = group.operations().unwrap();
+            for (rotation, translation) in operations
+                .rotations
+                .iter()
+                .zip(operations.translations.iter())
+            {
+                let operation = SymmetryOperation::new(*rotation, *translation);
+                let symbol = operation.to_string();
+                assert!(!symbol.contains('.'), "{}", symbol);
+                assert_eq!(symbol.parse::<SymmetryOperation>().unwrap(), operation);
+            }
+        }
+    }
+
+    #[test]
+    fn jones_notation_errors() {
+        for symbol in ["x,y", "x,y,z,x", "x,y,2z", "x,x,z", "x/2,y,z", "x,y,a"] {
+            let err = symbol.parse::<SymmetryOperation>().unwrap_err();
+            assert_eq!(err.input, symbol);
+        }
+        let operation: SymmetryOperation = "'x, -y, 1/2+z'".parse().unwrap();
+        assert_eq!(operation.to_string(), "x,-y,z+1/2");
+    }
+}
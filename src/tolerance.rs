@@ -0,0 +1,104 @@
+//! Tolerances of the symmetry search.
+
+/// Distance and angle tolerances used to find crystal symmetry.
+///
+/// A bare distance tolerance converts into a `Tolerance` without an angle tolerance,
+/// in which case spglib falls back to its default behaviour.
+///
+/// # Example
+///
+/// Get the dataset of a slightly distorted cubic cell with an explicit angle tolerance.
+///
+/// ```
+/// use spglib::cell::Cell;
+/// use spglib::dataset::Dataset;
+/// use spglib::tolerance::Tolerance;
+///
+/// let lattice = [[4.0, 0.0, 0.001], [0.0, 4.0, 0.0], [0.0, 0.0, 4.0]];
+/// let positions = [[0.0, 0.0, 0.0]];
+/// let types = [1];
+/// let cell = Cell::new(&lattice, &positions, &types);
+/// let tolerance = Tolerance::new(1e-2).with_angle_tolerance(1.0);
+/// let dataset = Dataset::new(&cell, tolerance).unwrap();
+/// assert_eq!(dataset.spacegroup_number, 221);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Tolerance {
+    /// Distance tolerance in Cartesian coordinates.
+    pub symprec: f64,
+    /// Angle tolerance in degrees.
+    pub angle_tolerance: Option<f64>,
+}
+
+impl Tolerance {
+    /// Returns a new tolerance with the default angle tolerance.
+    pub fn new(symprec: f64) -> Tolerance {
+        Tolerance {
+            symprec,
+            angle_tolerance: None,
+        }
+    }
+
+    /// Returns the tolerance with the given angle tolerance in degrees.
+    pub fn with_angle_tolerance(self, angle_tolerance: f64) -> Tolerance {
+        Tolerance {
+            angle_tolerance: Some(angle_tolerance),
+            ..self
+        }
+    }
+
+    // Returns the angle tolerance as expected by the `spgat_*` functions.
+    // A negative value selects the default behaviour of spglib.
+    pub(crate) fn raw_angle_tolerance(&self) -> f64 {
+        self.angle_tolerance.unwrap_or(-1.0)
+    }
+}
+
+impl From<f64> for Tolerance {
+    fn from(symprec: f64) -> Self {
+        Tolerance::new(symprec)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use spglib_sys as ffi;
+
+    use crate::cell::Cell;
+    use crate::dataset::Dataset;
+    use crate::tolerance::Tolerance;
+
+    fn distorted_cell() -> Cell {
+        // cubic cell with the c axis tilted by about 0.014 degrees
+        let lattice = [[4., 0., 0.001], [0., 4., 0.], [0., 0., 4.]];
+        Cell::new(&lattice, &[[0., 0., 0.]], &[1])
+    }
+
+    #[test]
+    fn default_angle_tolerance() {
+        let cell = distorted_cell();
+        let dataset = Dataset::new(&cell, 1e-2).unwrap();
+        let raw = unsafe {
+            ffi::spg_get_dataset(
+                cell.lattice.as_ptr() as *mut [f64; 3],
+                cell.positions.as_ptr() as *mut [f64; 3],
+                cell.types.as_ptr(),
+                1,
+                1e-2,
+            )
+        };
+        let (hall_number, n_operations) = unsafe { ((*raw).hall_number, (*raw).n_operations) };
+        unsafe { ffi::spg_free_dataset(raw) };
+        assert_eq!(dataset.hall_number, hall_number);
+        assert_eq!(dataset.n_operations, n_operations);
+    }
+
+    #[test]
+    fn strict_angle_tolerance() {
+        let cell = distorted_cell();
+        let loose = Dataset::new(&cell, Tolerance::new(1e-2).with_angle_tolerance(1.0)).unwrap();
+        assert_eq!(loose.spacegroup_number, 221);
+        let strict = Dataset::new(&cell, Tolerance::new(1e-2).with_angle_tolerance(1e-3)).unwrap();
+        assert!(strict.spacegroup_number < 221);
+    }
+}